@@ -0,0 +1,182 @@
+use std::{collections::HashMap, fmt::Display, path::Path};
+
+use crate::{error::Error, Answer, Day};
+
+/// Known-correct answers, loaded from a TOML file of the form:
+///
+/// ```toml
+/// [day01]
+/// part1 = 421
+/// part2 = 613
+///
+/// [day01.example]
+/// part1 = 11
+///
+/// [day01.example2]
+/// part2 = 31
+/// ```
+///
+/// The answers in `[dayNN]` are for the input, those in `[dayNN.example]` for the
+/// first example file, `[dayNN.example2]` for the second and so on.
+/// Answers can be given as integers or strings. Days or parts that are missing
+/// from the file are reported as unknown.
+pub struct Answers(HashMap<(Day, InputKind, u8), String>);
+
+/// Which input of a day an answer is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputKind {
+    /// The user's puzzle input
+    Real,
+    /// The n'th example, counting from 1
+    Example(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(String),
+    Unknown,
+}
+
+impl Verdict {
+    /// Short name of the verdict, for machine-readable output
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::Wrong(_) => "wrong",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Wrong(expected) => write!(f, "WRONG, expected {}", expected),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::Io(format!("Could not read answers file \"{:?}\"", path), e))?;
+        Self::parse(&text).map_err(|e| Error::InvalidAnswersFile(format!("\"{:?}\": {}", path, e)))
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let table: toml::Table = text.parse().map_err(|e| format!("{}", e))?;
+        let mut map = HashMap::new();
+        for (key, value) in table.iter() {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|s| s.parse::<Day>().ok())
+                .ok_or_else(|| format!("Invalid day in answers file: \"{}\"", key))?;
+            let parts = as_table(key, value)?;
+            for (part_key, answer) in parts.iter() {
+                let Some(example) = part_key.strip_prefix("example") else {
+                    let (part, answer) = parse_part(key, part_key, answer)?;
+                    map.insert((day, InputKind::Real, part), answer);
+                    continue;
+                };
+                let n = match example {
+                    "" => 1,
+                    n => n.parse().ok().filter(|&n| n > 1).ok_or_else(|| {
+                        format!("Invalid example in answers file: \"{}\"", part_key)
+                    })?,
+                };
+                // The key of the example table, for error messages
+                let key = format!("{}.{}", key, part_key);
+                for (part_key, answer) in as_table(&key, answer)?.iter() {
+                    let (part, answer) = parse_part(&key, part_key, answer)?;
+                    map.insert((day, InputKind::Example(n), part), answer);
+                }
+            }
+        }
+        Ok(Answers(map))
+    }
+
+    pub fn check(&self, day: Day, kind: InputKind, part: u8, answer: &Answer) -> Verdict {
+        let answer = match answer {
+            Answer::Solved(s) => s,
+            // An unimplemented part can't be wrong, so we don't check it
+            Answer::Unimplemented => return Verdict::Unknown,
+        };
+        match self.0.get(&(day, kind, part)) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong(expected.clone()),
+        }
+    }
+}
+
+fn as_table<'a>(key: &str, value: &'a toml::Value) -> Result<&'a toml::Table, String> {
+    value
+        .as_table()
+        .ok_or_else(|| format!("Entry \"{}\" in answers file is not a table", key))
+}
+
+// The part number and answer of an entry like `part1 = 421` in the table `key`
+fn parse_part(key: &str, part_key: &str, answer: &toml::Value) -> Result<(u8, String), String> {
+    let part = match part_key {
+        "part1" => 1,
+        "part2" => 2,
+        _ => return Err(format!("Invalid part in answers file: \"{}\"", part_key)),
+    };
+    let answer = match answer {
+        toml::Value::String(s) => s.clone(),
+        toml::Value::Integer(i) => i.to_string(),
+        _ => {
+            return Err(format!(
+                "Answer for {}.{} must be an integer or string",
+                key, part_key
+            ))
+        }
+    };
+    Ok((part, answer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, InputKind, Verdict};
+    use crate::{Answer, Day};
+
+    #[test]
+    fn test_answers() {
+        let answers = Answers::parse(
+            "[day01]\npart1 = 421\npart2 = \"abc\"\n\n[day01.example]\npart1 = 11\n\n\
+            [day01.example2]\npart2 = 31\n\n[day07]\npart1 = 3749\n",
+        )
+        .unwrap();
+        let day = |d| Day::new(d).unwrap();
+        let solved = |s: &str| Answer::Solved(s.to_owned());
+        let check = |d, part, answer| answers.check(day(d), InputKind::Real, part, &solved(answer));
+        assert_eq!(check(1, 1, "421"), Verdict::Correct);
+        assert_eq!(check(1, 2, "abc"), Verdict::Correct);
+        assert_eq!(check(1, 1, "420"), Verdict::Wrong("421".to_owned()));
+        assert_eq!(check(7, 2, "1"), Verdict::Unknown);
+        assert_eq!(check(2, 1, "1"), Verdict::Unknown);
+        assert_eq!(
+            answers.check(day(1), InputKind::Real, 2, &Answer::Unimplemented),
+            Verdict::Unknown
+        );
+
+        let example =
+            |n, part, answer| answers.check(day(1), InputKind::Example(n), part, &solved(answer));
+        assert_eq!(example(1, 1, "11"), Verdict::Correct);
+        assert_eq!(example(1, 2, "31"), Verdict::Unknown);
+        assert_eq!(example(2, 2, "30"), Verdict::Wrong("31".to_owned()));
+        assert_eq!(example(3, 1, "11"), Verdict::Unknown);
+
+        assert_eq!(
+            Answers::parse("[day26]\npart1 = 1\n").err().unwrap(),
+            "Invalid day in answers file: \"day26\""
+        );
+        assert!(Answers::parse("[dayone]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[day01]\npart3 = 1\n").is_err());
+        assert!(Answers::parse("[day01.example1]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[day01.example]\npart3 = 1\n").is_err());
+    }
+}
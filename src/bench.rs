@@ -0,0 +1,376 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    hint::black_box,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::Error,
+    get_registration,
+    runner::{parts, DayData},
+    Day, Year,
+};
+
+/// Each sample runs the function for at least this long, such that the resolution and
+/// overhead of the timer don't matter for fast functions
+pub const MIN_SAMPLE_TIME: Duration = Duration::from_millis(1);
+
+/// At most this many samples are taken, however long the measurement time
+pub const MAX_SAMPLES: usize = 10_000;
+
+/// Summary statistics of repeated timings of a function. Each sample times a batch of
+/// `iterations` runs, and the statistics are of the time per run in the samples.
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub samples: usize,
+    pub iterations: u64,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(samples: &mut [Duration], iterations: u64) -> Self {
+        assert!(!samples.is_empty());
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Stats {
+            samples: n,
+            iterations,
+            median,
+            mean,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+            min: samples[0],
+            max: samples[n - 1],
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "median {:.2?}, mean {:.2?} ± {:.2?}, min {:.2?}, max {:.2?} ({} samples of {} runs)",
+            self.median, self.mean, self.std_dev, self.min, self.max, self.samples, self.iterations
+        )
+    }
+}
+
+/// A separately timed stage of solving a day
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Part(u8),
+}
+
+impl Stage {
+    // Name of the stage in a baseline file
+    fn key(self) -> String {
+        match self {
+            Self::Parse => "parse_ns".to_owned(),
+            Self::Part(p) => format!("part{}_ns", p),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "parse_ns" => Some(Self::Parse),
+            "part1_ns" => Some(Self::Part(1)),
+            "part2_ns" => Some(Self::Part(2)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "Parse"),
+            Self::Part(p) => write!(f, "Part {}", p),
+        }
+    }
+}
+
+/// How days are benchmarked
+pub struct Options {
+    /// Only benchmark this part (and parsing)
+    pub part: Option<u8>,
+    /// Time to run each stage before measuring
+    pub warmup: Duration,
+    /// Approximate time to spend measuring each stage
+    pub measure: Duration,
+    /// Minimum number of samples per stage
+    pub min_samples: usize,
+    /// Baseline file from a previous run to compare against
+    pub baseline: Option<PathBuf>,
+    /// Save the median timings to this file, for use as a later baseline
+    pub save_baseline: Option<PathBuf>,
+}
+
+/// Time `f` repeatedly. It is first run for `options.warmup` time, which is also used to
+/// estimate how many runs fit in a sample of `MIN_SAMPLE_TIME`, and how many samples fit
+/// in `options.measure` time. At least `options.min_samples` samples are taken, and at
+/// most `MAX_SAMPLES`, unless the minimum is higher. The outputs of `f` are dropped after
+/// each sample, so dropping them is not timed.
+pub fn bench<R>(mut f: impl FnMut() -> R, options: &Options) -> Stats {
+    // The batches double in size, such that fast functions aren't slowed down by checking
+    // the time after every run
+    let begin = Instant::now();
+    let mut runs: u64 = 0;
+    let mut batch: u64 = 1;
+    while runs == 0 || begin.elapsed() < options.warmup {
+        for _ in 0..batch {
+            black_box(f());
+        }
+        runs += batch;
+        batch = batch.saturating_mul(2);
+    }
+    // At least a nanosecond, such that the number of iterations stays finite
+    let per_run = (begin.elapsed().as_secs_f64() / runs as f64).max(1e-9);
+    let iterations = (MIN_SAMPLE_TIME.as_secs_f64() / per_run).ceil() as u64;
+    let n_samples = (options.measure.as_secs_f64() / (per_run * iterations as f64)) as usize;
+    let n_samples = n_samples.min(MAX_SAMPLES).max(options.min_samples);
+    let mut outputs = Vec::with_capacity(iterations as usize);
+    let mut samples: Vec<Duration> = (0..n_samples)
+        .map(|_| {
+            let begin = Instant::now();
+            for _ in 0..iterations {
+                outputs.push(f());
+            }
+            let elapsed = begin.elapsed();
+            outputs.clear();
+            Duration::from_secs_f64(elapsed.as_secs_f64() / iterations as f64)
+        })
+        .collect();
+    Stats::from_samples(&mut samples, iterations)
+}
+
+/// The timings of a stage of a day
+pub struct StageStats {
+    pub stage: Stage,
+    pub stats: Stats,
+    /// Change of the median from the baseline in percent, if there is a baseline for
+    /// the stage. Positive values are regressions.
+    pub change: Option<f64>,
+}
+
+/// The timings of the stages of a day
+pub struct DayStats {
+    pub day: Day,
+    /// `None` if the day is not implemented
+    pub stages: Option<Vec<StageStats>>,
+    /// Parts that were to be benchmarked, but are not implemented
+    pub unimplemented: Vec<u8>,
+}
+
+/// Benchmark parsing and the parts of each day for its input, compare the timings with
+/// the baseline, and save them as a new baseline, if set in `options`. Each day is passed
+/// to `output` as soon as it is done, as benchmarking a day takes a while.
+pub fn bench_days(
+    year: Year,
+    data: Vec<DayData>,
+    options: &Options,
+    mut output: impl FnMut(&DayStats),
+) -> Result<Vec<DayStats>, Error> {
+    let baseline = options
+        .baseline
+        .as_deref()
+        .map(Baseline::load)
+        .transpose()?;
+    let mut new_baseline = Baseline::default();
+    let mut days = Vec::new();
+    for (day, maybe_data_solver) in data {
+        let Some((inputs, solver)) = maybe_data_solver else {
+            let day = DayStats {
+                day,
+                stages: None,
+                unimplemented: Vec::new(),
+            };
+            output(&day);
+            days.push(day);
+            continue;
+        };
+        let data = &inputs[0].data;
+        let puzzle = solver(data);
+        let implemented = get_registration(year, day)
+            .expect("Days with a solver are registered")
+            .parts;
+        let mut stages = vec![Stage::Parse];
+        let mut unimplemented = Vec::new();
+        for part in parts(options.part) {
+            // Don't benchmark parts that are not implemented
+            if implemented[part as usize - 1] {
+                stages.push(Stage::Part(part));
+            } else {
+                unimplemented.push(part);
+            }
+        }
+        let stages = stages
+            .into_iter()
+            .map(|stage| {
+                let stats = puzzle.bench(data, stage, options);
+                new_baseline.insert(day, stage, stats.median);
+                let change = baseline
+                    .as_ref()
+                    .and_then(|b| b.get(day, stage))
+                    .and_then(|old| percent_change(old, stats.median));
+                StageStats {
+                    stage,
+                    stats,
+                    change,
+                }
+            })
+            .collect();
+        let day = DayStats {
+            day,
+            stages: Some(stages),
+            unimplemented,
+        };
+        output(&day);
+        days.push(day);
+    }
+    if let Some(path) = &options.save_baseline {
+        new_baseline.save(path)?;
+    }
+    Ok(days)
+}
+
+/// Median timings of a previous benchmark run, stored as a TOML file with one table
+/// per day, e.g. `[day01]` with the keys `parse_ns`, `part1_ns` and `part2_ns`.
+#[derive(Default)]
+pub struct Baseline(HashMap<(Day, Stage), Duration>);
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::Io(format!("Could not read baseline file \"{:?}\"", path), e))?;
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e| Error::InvalidBaselineFile(format!("{:?}: {}", path, e)))?;
+        let mut map = HashMap::new();
+        let invalid = |key: &str| {
+            Error::InvalidBaselineFile(format!("{:?}: Invalid entry \"{}\"", path, key))
+        };
+        for (key, value) in table.iter() {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|s| s.parse::<Day>().ok())
+                .ok_or_else(|| invalid(key))?;
+            let stages = value.as_table().ok_or_else(|| invalid(key))?;
+            for (stage_key, nanos) in stages.iter() {
+                let stage = Stage::from_key(stage_key).ok_or_else(|| invalid(stage_key))?;
+                let nanos = nanos
+                    .as_integer()
+                    .filter(|&n| n >= 0)
+                    .ok_or_else(|| invalid(stage_key))?;
+                map.insert((day, stage), Duration::from_nanos(nanos as u64));
+            }
+        }
+        Ok(Baseline(map))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut table = toml::Table::new();
+        for (&(day, stage), median) in self.0.iter() {
+            let entry = table
+                .entry(format!("day{}", day))
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            entry
+                .as_table_mut()
+                .expect("Only tables are inserted at the top level")
+                .insert(stage.key(), toml::Value::Integer(median.as_nanos() as i64));
+        }
+        std::fs::write(path, table.to_string())
+            .map_err(|e| Error::Io(format!("Could not write baseline file \"{:?}\"", path), e))
+    }
+
+    pub fn get(&self, day: Day, stage: Stage) -> Option<Duration> {
+        self.0.get(&(day, stage)).copied()
+    }
+
+    pub fn insert(&mut self, day: Day, stage: Stage, median: Duration) {
+        self.0.insert((day, stage), median);
+    }
+}
+
+/// Relative change in percent from `old` to `new`. Positive values are regressions.
+/// There is no relative change from a zero baseline, which stages that take less than
+/// the timer's resolution can have.
+pub fn percent_change(old: Duration, new: Duration) -> Option<f64> {
+    if old.is_zero() {
+        return None;
+    }
+    Some(100.0 * (new.as_secs_f64() - old.as_secs_f64()) / old.as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bench, percent_change, Options, Stats, MIN_SAMPLE_TIME};
+    use std::time::Duration;
+
+    #[test]
+    fn test_stats() {
+        let mut samples: Vec<_> = [5, 1, 4, 2]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        let stats = Stats::from_samples(&mut samples, 100);
+        assert_eq!((stats.samples, stats.iterations), (4, 100));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(5));
+        assert_eq!(stats.std_dev.as_micros(), 1581);
+    }
+
+    #[test]
+    fn test_bench() {
+        let options = Options {
+            part: None,
+            warmup: Duration::from_millis(5),
+            measure: Duration::from_millis(20),
+            min_samples: 3,
+            baseline: None,
+            save_baseline: None,
+        };
+        let mut runs = 0;
+        let stats = bench(
+            || {
+                runs += 1;
+                vec![runs; 10]
+            },
+            &options,
+        );
+        assert!(stats.samples >= 3);
+        assert!(stats.iterations > 1);
+        // Samples are estimated to take at least the minimum time, so no more than fit
+        // in the measurement time are taken
+        assert!(
+            stats.samples <= (options.measure.as_micros() / MIN_SAMPLE_TIME.as_micros()) as usize
+        );
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+    }
+
+    #[test]
+    fn test_percent_change() {
+        let ms = Duration::from_millis;
+        assert_eq!(percent_change(ms(4), ms(5)), Some(25.0));
+        assert_eq!(percent_change(ms(4), ms(2)), Some(-50.0));
+        assert_eq!(percent_change(Duration::ZERO, ms(1)), None);
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use crate::{error::Error, session::SessionKey};
+
+/// A response stored in the cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub fetched: SystemTime,
+    pub status: u16,
+    pub body: String,
+}
+
+impl Entry {
+    pub fn age(&self) -> Duration {
+        // If the clock went backwards, the entry is as fresh as it gets
+        SystemTime::now()
+            .duration_since(self.fetched)
+            .unwrap_or_default()
+    }
+}
+
+// The 64-bit FNV-1a hash. Unlike the standard library's hashers, its output is fixed, so
+// the cache directory of a session key stays the same across Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// On-disk cache of HTTP responses, with one file per URL. Each file begins with a line
+/// with the status code and the time of the request in seconds since the Unix epoch,
+/// followed by the body of the response.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf) -> Self {
+        Cache { dir }
+    }
+
+    /// The cache for the given session key, in "advent2024" in the XDG cache directory.
+    /// This is `$XDG_CACHE_HOME`, or `$HOME/.cache` if that is not set.
+    /// Inputs differ per user, so each session key gets its own subdirectory.
+    pub fn for_session(session: &SessionKey) -> Option<Self> {
+        let cache_dir = std::env::var_os("XDG_CACHE_HOME")
+            .filter(|s| !s.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))?;
+        // Don't put the key itself in the path, so it doesn't leak through the file system
+        let subdir = format!("{:016x}", fnv1a(session.key.as_bytes()));
+        Some(Cache::new(cache_dir.join("advent2024").join(subdir)))
+    }
+
+    // E.g. "http://localhost:8080/2024/day/1" becomes "localhost_8080_2024_day_1"
+    fn path(&self, url: &str) -> PathBuf {
+        let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let name: String = url
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(name)
+    }
+
+    /// The cached response for the URL, if any. An unreadable entry is treated as missing.
+    pub fn get(&self, url: &str) -> Option<Entry> {
+        let text = std::fs::read_to_string(self.path(url)).ok()?;
+        let (header, body) = text.split_once('\n')?;
+        let (status, secs) = header.split_once(' ')?;
+        Some(Entry {
+            fetched: SystemTime::UNIX_EPOCH + Duration::from_secs(secs.parse().ok()?),
+            status: status.parse().ok()?,
+            body: body.to_owned(),
+        })
+    }
+
+    /// Remove the cached response for the URL. A missing entry is not an error.
+    pub fn remove(&self, url: &str) {
+        let _ = std::fs::remove_file(self.path(url));
+    }
+
+    pub fn insert(&self, url: &str, entry: &Entry) -> Result<(), Error> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| {
+            Error::Io(
+                format!("Could not create cache directory \"{:?}\"", self.dir),
+                e,
+            )
+        })?;
+        let secs = entry
+            .fetched
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path = self.path(url);
+        std::fs::write(&path, format!("{} {}\n{}", entry.status, secs, entry.body))
+            .map_err(|e| Error::Io(format!("Could not write cache file \"{:?}\"", path), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fnv1a, Cache, Entry};
+    use crate::test_util::TempDir;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_cache() {
+        let dir = TempDir::new("cache");
+        let cache = Cache::new(dir.path().to_owned());
+        let url = "https://adventofcode.com/2024/day/1/input";
+        assert_eq!(
+            cache.path(url),
+            dir.path().join("adventofcode_com_2024_day_1_input")
+        );
+        assert_eq!(cache.get(url), None);
+        let entry = Entry {
+            fetched: SystemTime::UNIX_EPOCH + Duration::from_secs(1733029200),
+            status: 200,
+            body: "3   4\n4   3\n".to_owned(),
+        };
+        cache.insert(url, &entry).unwrap();
+        assert_eq!(cache.get(url), Some(entry));
+        assert_eq!(cache.get("https://adventofcode.com/2024/day/2/input"), None);
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+    }
+}
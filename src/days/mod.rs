@@ -1,7 +1,33 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
+use crate::Registration;
+
+// Declares each day module of a year, and registers its solution. Each year is a
+// module `yYYYY` which invokes this macro with its year, followed by a line per day
+// with its day number, module and the type implementing `Solution`. If the type also
+// implements `Visualize`, add `+ Visualize` to register its visualization.
+macro_rules! days {
+    ($year:literal; $($day:literal => $module:ident::$solution:ident $(+ $visualize:ident)?,)*) => {
+        $(pub mod $module;)*
+
+        pub(crate) static REGISTRY: [Registration; [$($day),*].len()] = [$(
+            Registration::new::<$module::$solution>($year, $day)
+                $(.with_visualizer(
+                    <$module::$solution as crate::visualize::$visualize>::visualize
+                ))?
+        ),*];
+
+        // The registry must be sorted by day, with no duplicates
+        const _: () = {
+            let days: &[u8] = &[$($day),*];
+            let mut i = 1;
+            while i < days.len() {
+                assert!(days[i - 1] < days[i]);
+                i += 1;
+            }
+        };
+    };
+}
+
+pub mod y2024;
+
+// The registries of each year, sorted by year
+pub(crate) static REGISTRY: &[&[Registration]] = &[&y2024::REGISTRY];
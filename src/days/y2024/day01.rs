@@ -0,0 +1,104 @@
+use crate::{
+    parse::{integers, ParseError},
+    Solution,
+};
+use std::cmp::Ordering;
+
+struct SameIter<'a> {
+    next: &'a [i64],
+}
+
+impl Iterator for SameIter<'_> {
+    type Item = (usize, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let fst = if let Some(x) = self.next.first() {
+            *x
+        } else {
+            return None;
+        };
+        unsafe {
+            // Safety: We just checked there is at least one element
+            let rest = self.next.split_at_unchecked(1).1;
+            let first_diff = rest.iter().position(|&i| i != fst).unwrap_or(rest.len());
+            // Safety: The result of `position` is always in [0, self.len()), and the alternative value
+            // of rest.len() is also inbounds.
+            self.next = rest.split_at_unchecked(first_diff).1;
+            Some((first_diff + 1, fst))
+        }
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    // Both lists, sorted
+    type Parsed = (Vec<i64>, Vec<i64>);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(s: &str) -> Self::Parsed {
+        parse(s).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn part_one((left, right): &Self::Parsed) -> i64 {
+        left.iter()
+            .zip(right.iter())
+            .map(|(i, j)| (i - j).abs())
+            .sum()
+    }
+
+    fn part_two((left, right): &Self::Parsed) -> i64 {
+        let mut lefts = SameIter { next: left };
+        let mut rights = SameIter { next: right };
+        let mut elem_left = lefts.next();
+        let mut elem_right = rights.next();
+        let mut p2 = 0;
+        while let (Some((ln, lx)), Some((rn, rx))) = (elem_left, elem_right) {
+            match lx.cmp(&rx) {
+                Ordering::Less => {
+                    elem_left = lefts.next();
+                }
+                Ordering::Greater => {
+                    elem_right = rights.next();
+                }
+                Ordering::Equal => {
+                    p2 += lx * (ln * rn) as i64;
+                    elem_left = lefts.next();
+                    elem_right = rights.next();
+                }
+            }
+        }
+        p2
+    }
+}
+
+// The numbers come in pairs, one of each list
+fn parse(s: &str) -> Result<(Vec<i64>, Vec<i64>), ParseError> {
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    let mut numbers = integers(s);
+    while let Some(a) = numbers.next() {
+        left.push(a?);
+        right.push(numbers.expect_next()?);
+    }
+    left.sort_unstable();
+    right.sort_unstable();
+    Ok((left, right))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solution;
+
+    static TEST_STR: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn test() {
+        assert_eq!(super::Day01::solve(TEST_STR), (11, 31));
+    }
+}
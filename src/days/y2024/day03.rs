@@ -0,0 +1,71 @@
+use crate::Solution;
+
+pub struct Day03;
+
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
+
+impl Solution for Day03 {
+    type Parsed = Vec<Instruction>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(s: &str) -> Self::Parsed {
+        let re = regex::Regex::new(r"mul\((\d+),(\d+)\)|do\(\)|don't\(\)").unwrap();
+        re.captures_iter(s)
+            .map(|cap| {
+                let whole = &cap[0];
+                if whole.starts_with("mul(") {
+                    Instruction::Mul(cap[1].parse().unwrap(), cap[2].parse().unwrap())
+                } else if whole.starts_with("do()") {
+                    Instruction::Do
+                } else {
+                    Instruction::Dont
+                }
+            })
+            .collect()
+    }
+
+    fn part_one(instructions: &Self::Parsed) -> u32 {
+        instructions
+            .iter()
+            .map(|i| match i {
+                Instruction::Mul(a, b) => a * b,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    fn part_two(instructions: &Self::Parsed) -> u32 {
+        let mut p2 = 0;
+        let mut enabled = true;
+        for i in instructions {
+            match i {
+                Instruction::Mul(a, b) => p2 += a * b * (enabled as u32),
+                Instruction::Do => enabled = true,
+                Instruction::Dont => enabled = false,
+            }
+        }
+        p2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solution;
+
+    static TEST_STR_1: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+    static TEST_STR_2: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test() {
+        assert_eq!(super::Day03::solve(TEST_STR_1).0, 161);
+        assert_eq!(super::Day03::solve(TEST_STR_2).1, 48);
+    }
+}
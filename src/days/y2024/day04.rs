@@ -0,0 +1,67 @@
+use crate::{
+    grid::{Grid, Offset, DIAGONALS, NEIGHBOURS_8},
+    Solution,
+};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(s: &str) -> Self::Parsed {
+        Grid::parse(s, |b| b)
+    }
+
+    fn part_one(grid: &Self::Parsed) -> usize {
+        // From each X, look for the rest of the word in every direction
+        grid.iter()
+            .filter(|&(_, &b)| b == b'X')
+            .map(|(pos, _)| {
+                NEIGHBOURS_8
+                    .into_iter()
+                    .filter(|&offset| grid.ray(pos, offset).take(3).map(|p| grid[p]).eq(*b"MAS"))
+                    .count()
+            })
+            .sum()
+    }
+
+    fn part_two(grid: &Self::Parsed) -> usize {
+        // An A is the center of an X-MAS if two of its diagonals read MAS, going through it
+        grid.iter()
+            .filter(|&(_, &b)| b == b'A')
+            .filter(|&(pos, _)| {
+                let corner = |offset: Offset| grid.step(pos, offset).map(|p| grid[p]);
+                DIAGONALS
+                    .into_iter()
+                    .filter(|&(dr, dc)| {
+                        corner((dr, dc)) == Some(b'M') && corner((-dr, -dc)) == Some(b'S')
+                    })
+                    .count()
+                    > 1
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solution;
+
+    static TEST_STR: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn test() {
+        assert_eq!(super::Day04::solve(TEST_STR), (18, 9));
+    }
+}
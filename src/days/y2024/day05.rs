@@ -0,0 +1,115 @@
+use crate::{
+    parse::{integers, ParseError},
+    Solution,
+};
+use std::{cmp::Ordering, collections::HashSet};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    // The ordering rules, and the updates
+    type Parsed = (HashSet<(u8, u8)>, Vec<Vec<u8>>);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(s: &str) -> Self::Parsed {
+        parse(s).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn part_one((order, updates): &Self::Parsed) -> u32 {
+        updates
+            .iter()
+            .filter(|v| is_sorted(v, order))
+            .map(|v| v[v.len() / 2] as u32)
+            .sum()
+    }
+
+    fn part_two((order, updates): &Self::Parsed) -> u32 {
+        let mut v: Vec<u8> = Vec::new();
+        updates
+            .iter()
+            .filter(|u| !is_sorted(u, order))
+            .map(|u| {
+                v.clear();
+                v.extend_from_slice(u);
+                v.sort_by(|&a, &b| {
+                    if order.contains(&(a, b)) {
+                        Ordering::Less
+                    } else if order.contains(&(b, a)) {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Equal
+                    }
+                });
+                v[v.len() / 2] as u32
+            })
+            .sum()
+    }
+}
+
+fn parse(s: &str) -> Result<<Day05 as Solution>::Parsed, ParseError> {
+    let delimiter = s.find("\n\n").expect("No empty line between rules and updates");
+    // Each rule is a pair of numbers like "47|53"
+    let mut order = HashSet::new();
+    let mut numbers = integers(&s[..delimiter]);
+    while let Some(x) = numbers.next() {
+        order.insert((x?, numbers.expect_next()?));
+    }
+    // The updates start after the rules and the empty line
+    let first_line = s[..delimiter].lines().count() + 2;
+    let updates = s[delimiter + 2..]
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let v: Vec<u8> = integers(line)
+                .on_line(first_line + i)
+                .collect::<Result<_, _>>()?;
+            assert!((v.len() & 1) == 1); // is odd
+            Ok(v)
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((order, updates))
+}
+
+fn is_sorted(v: &[u8], order: &HashSet<(u8, u8)>) -> bool {
+    v.is_sorted_by(|&a, &b| !order.contains(&(b, a)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solution;
+
+    static TEST_STR: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+    #[test]
+    fn test() {
+        assert_eq!(super::Day05::solve(TEST_STR), (143, 123));
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{
+    grid::{Direction, Grid, Pos},
+    visualize::{Animation, Tile, Visualize},
+    Solution,
+};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    // The maze, and the guard's initial position and direction
+    type Parsed = (Grid<Cell>, (Pos, Direction));
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(s: &str) -> Self::Parsed {
+        parse(s)
+    }
+
+    fn part_one((maze, guard): &Self::Parsed) -> usize {
+        // The starting position, plus each newly visited cell
+        1 + first_visits(maze, *guard).len()
+    }
+
+    fn part_two((maze, guard): &Self::Parsed) -> usize {
+        looping_obstacles(maze, *guard).len()
+    }
+}
+
+// The positions where a new obstacle makes the guard walk in a loop, in the order the
+// guard first visits them
+fn looping_obstacles(maze: &Grid<Cell>, guard: (Pos, Direction)) -> Vec<Pos> {
+    // A new obstacle can only change the path if it is on the path, so only the
+    // visited cells need to be tried. The path up to the first visit of the obstacle's
+    // cell is the same as without it, so each guard can start from just before it.
+    let jumps = jump_table(maze);
+    // The stamp of the obstacle being tried when each state was last seen. This is
+    // cheaper than clearing the states for every obstacle.
+    let mut seen: Grid<[usize; 4]> = maze.map(|_| [0; 4]);
+    first_visits(maze, guard)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, (start, start_dir))| {
+            let stamp = i + 1;
+            let obstacle = maze
+                .step(start, start_dir.offset())
+                .expect("The guard steps into the obstacle's cell, so it is in the maze");
+            let (mut pos, mut dir) = (start, start_dir);
+            loop {
+                let states = &mut seen[pos];
+                if states[dir as usize] == stamp {
+                    return Some(obstacle);
+                }
+                states[dir as usize] = stamp;
+                let stop = if ahead(pos, dir, obstacle)
+                    && jumps[pos][dir as usize].is_none_or(|stop| !ahead(stop, dir, obstacle))
+                {
+                    maze.step(obstacle, dir.reverse().offset())
+                } else {
+                    jumps[pos][dir as usize]
+                };
+                match stop {
+                    None => return None,
+                    Some(stop) => (pos, dir) = (stop, dir.turn_right()),
+                }
+            }
+        })
+        .collect()
+}
+
+const FREE: Tile = Tile::new('.', [60, 60, 60]);
+const OBSTACLE: Tile = Tile::new('#', [170, 170, 170]);
+const NEW_OBSTACLE: Tile = Tile::new('O', [255, 70, 70]);
+// Cells the guard has walked through vertically, horizontally, or both
+const TRAILS: [Tile; 3] = [
+    Tile::new('│', [80, 140, 255]),
+    Tile::new('─', [80, 200, 120]),
+    Tile::new('┼', [200, 120, 255]),
+];
+
+fn guard_tile(dir: Direction) -> Tile {
+    let glyph = match dir {
+        Direction::Up => '↑',
+        Direction::Right => '→',
+        Direction::Down => '↓',
+        Direction::Left => '←',
+    };
+    Tile::new(glyph, [255, 215, 0])
+}
+
+impl Visualize for Day06 {
+    // The guard walks its path, leaving a trail. For part 2, the first new obstacle that
+    // makes the guard loop is placed, and the guard walks until it starts repeating itself.
+    // The walk is simulated step by step, independently of the jump table of part 2.
+    fn visualize(input: &str, part: u8) -> Animation {
+        let (maze, guard) = parse(input);
+        let obstacle = if part == 2 {
+            looping_obstacles(&maze, guard).first().copied()
+        } else {
+            None
+        };
+        let mut grid = maze.map(|&c| match c {
+            Cell::Free => FREE,
+            Cell::Obstacle => OBSTACLE,
+        });
+        if let Some(obstacle) = obstacle {
+            grid[obstacle] = NEW_OBSTACLE;
+        }
+        let mut animation = Animation::new(grid);
+        // The ways the guard walked through each cell, as bits: 1 is vertically, 2 horizontally
+        let mut trail = maze.map(|_| 0);
+        let mut seen = maze.map(|_| [false; 4]);
+        let (mut pos, mut dir) = guard;
+        animation.set(pos, guard_tile(dir));
+        animation.step();
+        while !std::mem::replace(&mut seen[pos][dir as usize], true) {
+            trail[pos] |= match dir {
+                Direction::Up | Direction::Down => 1,
+                Direction::Left | Direction::Right => 2,
+            };
+            match maze.step(pos, dir.offset()) {
+                None => {
+                    animation.set(pos, TRAILS[trail[pos] - 1]);
+                    animation.step();
+                    break;
+                }
+                Some(next) if maze[next] == Cell::Obstacle || Some(next) == obstacle => {
+                    dir = dir.turn_right();
+                }
+                Some(next) => {
+                    animation.set(pos, TRAILS[trail[pos] - 1]);
+                    pos = next;
+                }
+            }
+            animation.set(pos, guard_tile(dir));
+            animation.step();
+        }
+        animation
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Free,
+    Obstacle,
+}
+
+fn parse(s: &str) -> (Grid<Cell>, (Pos, Direction)) {
+    let bytes = Grid::parse(s, |b| b);
+    let pos = bytes
+        .position(|&b| Direction::from_arrow(b).is_some())
+        .expect("No guard in the maze");
+    let dir = Direction::from_arrow(bytes[pos]).expect("The guard was found by its arrow");
+    let maze = bytes.map(|&b| match b {
+        b'#' => Cell::Obstacle,
+        b'.' => Cell::Free,
+        b if Direction::from_arrow(b).is_some() => Cell::Free,
+        b => panic!("Invalid byte in maze: {:?}", b as char),
+    });
+    (maze, (pos, dir))
+}
+
+// Walk the guard out of the maze. Returns the position and direction of the guard each
+// time it is about to step into a cell it has not visited before.
+fn first_visits(maze: &Grid<Cell>, (mut pos, mut dir): (Pos, Direction)) -> Vec<(Pos, Direction)> {
+    let mut visited = maze.map(|_| false);
+    visited[pos] = true;
+    let mut states = Vec::new();
+    while let Some(next) = maze.step(pos, dir.offset()) {
+        if maze[next] == Cell::Obstacle {
+            dir = dir.turn_right();
+        } else {
+            if !visited[next] {
+                visited[next] = true;
+                states.push((pos, dir));
+            }
+            pos = next;
+        }
+    }
+    states
+}
+
+// For each cell and direction, where a guard walking from the cell in the direction stops
+// in front of an obstacle, or `None` if it walks out of the maze
+fn jump_table(maze: &Grid<Cell>) -> Grid<[Option<Pos>; 4]> {
+    let mut jumps = maze.map(|_| [None; 4]);
+    let positions: Vec<Pos> = maze.positions().collect();
+    for dir in Direction::ALL {
+        // The stop of a cell is found from the stop of the next cell, so the next cell
+        // must be done first. Going up or left, it comes earlier row by row.
+        let order: Box<dyn Iterator<Item = &Pos>> = match dir {
+            Direction::Up | Direction::Left => Box::new(positions.iter()),
+            Direction::Down | Direction::Right => Box::new(positions.iter().rev()),
+        };
+        for &pos in order {
+            jumps[pos][dir as usize] = match maze.step(pos, dir.offset()) {
+                None => None,
+                Some(next) if maze[next] == Cell::Obstacle => Some(pos),
+                Some(next) => jumps[next][dir as usize],
+            };
+        }
+    }
+    jumps
+}
+
+// Whether `other` is in front of `pos` when facing the direction, on the same row or column
+fn ahead(pos: Pos, dir: Direction, other: Pos) -> bool {
+    match dir {
+        Direction::Up => other.col == pos.col && other.row < pos.row,
+        Direction::Down => other.col == pos.col && other.row > pos.row,
+        Direction::Left => other.row == pos.row && other.col < pos.col,
+        Direction::Right => other.row == pos.row && other.col > pos.col,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solution;
+
+    static TEST_STR: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    #[test]
+    fn test() {
+        assert_eq!(super::Day06::solve(TEST_STR), (41, 6));
+    }
+}
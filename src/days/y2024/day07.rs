@@ -1,33 +1,60 @@
+use crate::{
+    parse::{integers, ParseError},
+    Solution,
+};
 use std::ops::RangeInclusive;
 
-pub fn solve(s: &str) -> (u64, u64) {
-    let mut v: Vec<u64> = Vec::new();
+pub struct Day07;
+
+impl Solution for Day07 {
+    // Each equation's target value and its operands
+    type Parsed = Vec<(u64, Vec<u64>)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(s: &str) -> Self::Parsed {
+        s.lines()
+            .enumerate()
+            .map(|(i, line)| {
+                // The target value, followed by the operands
+                let mut numbers = integers(line).on_line(i + 1);
+                let target = numbers.expect_next()?;
+                let v: Vec<u64> = numbers.collect::<Result<_, _>>()?;
+                assert!(!v.is_empty());
+                Ok((target, v))
+            })
+            .collect::<Result<_, ParseError>>()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn part_one(equations: &Self::Parsed) -> u64 {
+        sum_solvable(equations, false)
+    }
+
+    fn part_two(equations: &Self::Parsed) -> u64 {
+        sum_solvable(equations, true)
+    }
+}
+
+fn sum_solvable(equations: &[(u64, Vec<u64>)], part2: bool) -> u64 {
     let mut r: Vec<RangeInclusive<u64>> = Vec::new();
-    s.lines().fold((0u64, 0u64), |(p1, p2), line| {
-        let (target_str, rest) = line.split_once(": ").unwrap();
-        let target = target_str.parse::<u64>().unwrap();
-        v.clear();
-        r.clear();
-        v.extend(
-            rest.split_ascii_whitespace()
-                .map(|s| s.parse::<u64>().unwrap()),
-        );
-        let (first, rest) = v.split_first().unwrap();
-        let (mut smaller, mut larger) = (*first, *first);
-        r.push(smaller..=larger);
-        for &i in rest {
-            smaller += i * (i.min(smaller) != 1) as u64;
-            larger = larger * 10u64.pow(i.ilog10() + 1) + i;
+    equations
+        .iter()
+        .filter(|(target, v)| {
+            r.clear();
+            let (first, rest) = v.split_first().unwrap();
+            let (mut smaller, mut larger) = (*first, *first);
             r.push(smaller..=larger);
-        }
-        if solves(&v, &r, target, false) {
-            (p1 + target, p2 + target)
-        } else if solves(&v, &r, target, true) {
-            (p1, p2 + target)
-        } else {
-            (p1, p2)
-        }
-    })
+            for &i in rest {
+                smaller += i * (i.min(smaller) != 1) as u64;
+                larger = larger * 10u64.pow(i.ilog10() + 1) + i;
+                r.push(smaller..=larger);
+            }
+            // Solutions without concatenation are cheaper to find, so check these first
+            solves(v, &r, *target, false) || (part2 && solves(v, &r, *target, true))
+        })
+        .map(|(target, _)| target)
+        .sum()
 }
 
 // The trick here is to solve it recursively, and to prune away the recursive
@@ -56,7 +83,7 @@ fn solves(v: &[u64], ranges: &[RangeInclusive<u64>], target: u64, part2: bool) -
     // Check all solutions where last element is multiplied to the rest.
     // If last doesn't divide target, then there can be no possible solution where
     // rest adds to some number N, and then N * last == target, so we can skip that
-    if target % last == 0 && solves(rest, rest_range, target / last, part2) {
+    if target.is_multiple_of(last) && solves(rest, rest_range, target / last, part2) {
         return true;
     }
     if part2 {
@@ -76,6 +103,8 @@ fn solves(v: &[u64], ranges: &[RangeInclusive<u64>], target: u64, part2: bool) -
 
 #[cfg(test)]
 mod tests {
+    use crate::Solution;
+
     static TEST_STR: &str = "190: 10 19
 3267: 81 40 27
 83: 17 5
@@ -88,6 +117,6 @@ mod tests {
 
     #[test]
     fn test() {
-        assert_eq!(super::solve(TEST_STR), (3749, 11387));
+        assert_eq!(super::Day07::solve(TEST_STR), (3749, 11387));
     }
 }
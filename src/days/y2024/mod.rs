@@ -0,0 +1,12 @@
+use crate::Registration;
+
+days! {
+    2024;
+    1 => day01::Day01,
+    2 => day02::Day02,
+    3 => day03::Day03,
+    4 => day04::Day04,
+    5 => day05::Day05,
+    6 => day06::Day06 + Visualize,
+    7 => day07::Day07,
+}
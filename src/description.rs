@@ -0,0 +1,211 @@
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    download::{unescape_html, unlock_time, Downloader},
+    error::Error,
+    submit::BASE_URL,
+    Day, Year,
+};
+
+/// The puzzle page only shows part 2 once part 1 is solved. A cached page without part 2
+/// is refreshed if it is older than this, such that part 2 shows up soon after.
+pub const PART_ONE_MAX_AGE: Duration = Duration::from_secs(30);
+
+/// Fetch the puzzle page of the day, and convert its description to Markdown
+pub fn fetch(downloader: &mut Downloader, year: Year, day: Day) -> Result<String, Error> {
+    if SystemTime::now() < unlock_time(year, day) {
+        return Err(Error::NotYetReleased(day));
+    }
+    let path = format!("/{}/day/{}", year, day.get());
+    // A page with both parts never changes, so only a page with just part 1 may be stale
+    let mut resp = downloader.get(&path, None)?;
+    if resp.is_success() && articles(&resp.body).len() < 2 {
+        resp = downloader.get(&path, Some(PART_ONE_MAX_AGE))?;
+    }
+    if !resp.is_success() {
+        return Err(Error::Server(resp.body));
+    }
+    let markdown = to_markdown(&resp.body, &format!("{}{}", BASE_URL, path));
+    if markdown.is_empty() {
+        downloader.forget(&path);
+        return Err(Error::Server(format!(
+            "The page of day {} has no puzzle description",
+            day
+        )));
+    }
+    Ok(markdown)
+}
+
+// Each part of the puzzle is in its own <article>
+fn articles(html: &str) -> Vec<&str> {
+    let re = regex::Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    re.captures_iter(html)
+        .map(|cap| cap.get(1).expect("The group is not optional").as_str())
+        .collect()
+}
+
+/// Convert the puzzle description in an AoC puzzle page to Markdown, which reads fine as
+/// plain text in a terminal. Emphasis becomes `*`, code becomes backticks or fenced
+/// blocks, and links are made absolute using `url`, the URL of the page.
+/// Everything outside the description, like the answers to solved parts, is left out.
+pub fn to_markdown(html: &str, url: &str) -> String {
+    let markdown: Vec<String> = articles(html)
+        .into_iter()
+        .map(|article| Converter::new(url).convert(article))
+        .collect();
+    markdown.join("\n")
+}
+
+struct Converter<'a> {
+    url: &'a str,
+    out: String,
+    in_pre: bool,
+    in_code: bool,
+    // Targets of the links we are inside, innermost last
+    links: Vec<String>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(url: &'a str) -> Self {
+        Converter {
+            url,
+            out: String::new(),
+            in_pre: false,
+            in_code: false,
+            links: Vec::new(),
+        }
+    }
+
+    fn convert(mut self, html: &str) -> String {
+        let mut rest = html;
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('<') {
+                let end = after.find('>').unwrap_or(after.len());
+                self.tag(&after[..end]);
+                rest = after.get(end + 1..).unwrap_or_default();
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.text(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+        let trimmed = self.out.trim_end();
+        if trimmed.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", trimmed)
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let closing = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match (name.as_str(), closing) {
+            ("h2", false) => {
+                self.block();
+                self.out.push_str("## ");
+            }
+            ("p" | "h2" | "ul", _) => self.block(),
+            ("pre", false) => {
+                self.block();
+                self.out.push_str("```\n");
+                self.in_pre = true;
+            }
+            ("pre", true) => {
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out.push_str("```");
+                self.block();
+                self.in_pre = false;
+            }
+            // Code blocks are <pre><code>, where the fence replaces the backticks
+            ("code", _) if !self.in_pre => {
+                self.out.push('`');
+                self.in_code = !closing;
+            }
+            // Markdown has no emphasis in code
+            ("em", _) if !self.in_pre && !self.in_code => self.out.push('*'),
+            ("a", false) => {
+                self.out.push('[');
+                self.links
+                    .push(self.resolve(attribute(tag, "href").unwrap_or_default()));
+            }
+            ("a", true) => {
+                let link = self.links.pop().unwrap_or_default();
+                self.out.push_str(&format!("]({})", link));
+            }
+            ("li", false) => self.out.push_str("- "),
+            ("li", true) => self.out.push('\n'),
+            // Other tags, like <span> for hover text, only style the text inside them
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let text = unescape_html(text);
+        if self.in_pre {
+            self.out.push_str(&text);
+            return;
+        }
+        // Outside code blocks, whitespace is collapsed like in a browser
+        let words: Vec<&str> = text.split_ascii_whitespace().collect();
+        let at_start = self.out.is_empty() || self.out.ends_with([' ', '\n']);
+        if text.starts_with(|c: char| c.is_ascii_whitespace()) && !at_start {
+            self.out.push(' ');
+        }
+        self.out.push_str(&words.join(" "));
+        if text.ends_with(|c: char| c.is_ascii_whitespace()) && !words.is_empty() {
+            self.out.push(' ');
+        }
+    }
+
+    // Start or end a block, which is separated from the previous one by an empty line
+    fn block(&mut self) {
+        self.out.truncate(self.out.trim_end_matches(' ').len());
+        if !self.out.is_empty() {
+            while !self.out.ends_with("\n\n") {
+                self.out.push('\n');
+            }
+        }
+    }
+
+    // Links on AoC are usually relative, like "/2024/stats" or "1/input"
+    fn resolve(&self, href: &str) -> String {
+        let href = unescape_html(href);
+        if href.contains("://") || href.starts_with('#') {
+            href
+        } else if href.starts_with('/') {
+            format!("{}{}", BASE_URL, href)
+        } else {
+            let dir = self.url.rsplit_once('/').map_or(self.url, |(dir, _)| dir);
+            format!("{}/{}", dir, href)
+        }
+    }
+}
+
+fn attribute<'t>(tag: &'t str, name: &str) -> Option<&'t str> {
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_markdown;
+
+    #[test]
+    fn test_to_markdown() {
+        let url = "https://adventofcode.com/2024/day/1";
+        assert_eq!(
+            to_markdown(include_str!("../fixtures/day_page.html"), url),
+            include_str!("../fixtures/day_page.md")
+        );
+        assert_eq!(to_markdown("<html><body>404</body></html>", url), "");
+    }
+}
@@ -0,0 +1,394 @@
+use reqwest::blocking::Client;
+use std::{
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    cache::{self, Cache},
+    error::Error,
+    session::{self, SessionKey, Source},
+    submit::BASE_URL,
+    Day, Year,
+};
+
+/// Sent with every request, unless `user_agent` is set in the config file. The AoC
+/// maintainers ask that automated tools identify themselves, preferably with contact info.
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "advent2024/",
+    env!("CARGO_PKG_VERSION"),
+    " (Advent of Code runner, Rust reqwest)"
+);
+
+pub enum Downloaded {
+    NotYetReleased,
+    Data(String),
+}
+
+/// The time the day is released, which is midnight US Eastern time. AoC is in December,
+/// when US Eastern time is always UTC-5.
+pub fn unlock_time(year: Year, day: Day) -> SystemTime {
+    let y = year.get() as u64;
+    // Number of leap years in 1..=y
+    let leap_years = |y: u64| y / 4 - y / 100 + y / 400;
+    let is_leap = leap_years(y) != leap_years(y - 1);
+    // Days from 1970-01-01 to December 1st, then to the day
+    let days = 365 * (y - 1970) + leap_years(y - 1) - leap_years(1969)
+        + 334
+        + is_leap as u64
+        + (day.get() as u64 - 1);
+    SystemTime::UNIX_EPOCH + Duration::from_secs(days * 24 * 3600 + 5 * 3600)
+}
+
+/// The user agent from the config file, or else the default
+pub fn user_agent() -> Result<String, Error> {
+    Ok(session::config_string("user_agent")?
+        .map_or_else(|| DEFAULT_USER_AGENT.to_owned(), |(ua, _)| ua))
+}
+
+pub fn make_client(session: &SessionKey, user_agent: &str) -> Result<Client, Error> {
+    // Other users on the machine should not be able to read the key
+    if let Source::ConfigFile(path) = &session.source {
+        if session::has_loose_permissions(path) {
+            eprintln!(
+                "Warning: Config file {:?} with session key is readable by other users. \
+                Consider running `chmod 600` on it.",
+                path
+            );
+        }
+    }
+    let session = session.key.as_str();
+    // Verify it's formatted correctly
+    let bytes = session.as_bytes();
+    if bytes.len() != 128 || !bytes.iter().all(|&b| b.is_ascii_hexdigit()) {
+        return Err(Error::InvalidSessionKey);
+    };
+    let mut headers = reqwest::header::HeaderMap::default();
+    let cookie = reqwest::header::HeaderValue::from_str(format!("session={}", session).as_str())
+        .expect("An ASCII hex session key is a valid header value");
+    headers.insert("Cookie", cookie);
+    Ok(Client::builder()
+        .default_headers(headers)
+        .user_agent(user_agent)
+        .build()?)
+}
+
+/// How the downloader treats the server
+pub struct Options {
+    pub base_url: String,
+    /// Minimum time between the start of two requests
+    pub min_interval: Duration,
+    /// Number of times a request is retried if the server has an error (status 5xx)
+    pub retries: u32,
+    /// Wait before the first retry. It is doubled for each subsequent retry.
+    pub backoff: Duration,
+    /// If `None`, responses are not cached
+    pub cache: Option<Cache>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            base_url: BASE_URL.to_owned(),
+            min_interval: Duration::from_secs(1),
+            retries: 3,
+            backoff: Duration::from_secs(2),
+            cache: None,
+        }
+    }
+}
+
+/// The status of a cached failed attempt, which is not a real HTTP status
+pub const FAILED: u16 = 0;
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Makes GET requests to the AoC server, politely: Requests are spaced out, server errors
+/// are retried with backoff, and responses are cached on disk where possible.
+pub struct Downloader {
+    client: Client,
+    options: Options,
+    last_request: Option<Instant>,
+}
+
+impl Downloader {
+    pub fn new(client: Client, options: Options) -> Self {
+        Downloader {
+            client,
+            options,
+            last_request: None,
+        }
+    }
+
+    /// Get the path, e.g. "/2024/day/1", on the server. Successful responses are cached,
+    /// and a cached response is used if it is younger than `max_age`, or regardless of age
+    /// if `max_age` is `None`.
+    pub fn get(&mut self, path: &str, max_age: Option<Duration>) -> Result<Response, Error> {
+        let url = format!("{}{}", self.options.base_url, path);
+        if let Some(entry) = self.options.cache.as_ref().and_then(|c| c.get(&url)) {
+            if max_age.is_none_or(|max_age| entry.age() < max_age) {
+                return Ok(Response {
+                    status: entry.status,
+                    body: entry.body,
+                });
+            }
+        }
+        let response = self.fetch(&url)?;
+        if let Some(cache) = &self.options.cache {
+            if response.is_success() {
+                let entry = cache::Entry {
+                    fetched: SystemTime::now(),
+                    status: response.status,
+                    body: response.body.clone(),
+                };
+                // The response is still usable even if it can't be cached
+                if let Err(e) = cache.insert(&url, &entry) {
+                    eprintln!("Warning: {}", e);
+                }
+            }
+        }
+        Ok(response)
+    }
+
+    pub fn has_cache(&self) -> bool {
+        self.options.cache.is_some()
+    }
+
+    /// Record a failed attempt to get the path, as a cached response with status `FAILED`
+    /// and no body. `get` returns it like any other cached response while it is younger
+    /// than `max_age`, such that failures are not retried more often than successes.
+    pub fn cache_failure(&self, path: &str) {
+        if let Some(cache) = &self.options.cache {
+            let entry = cache::Entry {
+                fetched: SystemTime::now(),
+                status: FAILED,
+                body: String::new(),
+            };
+            if let Err(e) = cache.insert(&format!("{}{}", self.options.base_url, path), &entry) {
+                eprintln!("Warning: {}", e);
+            }
+        }
+    }
+
+    /// Remove the cached response of the path, if any
+    pub fn forget(&self, path: &str) {
+        if let Some(cache) = &self.options.cache {
+            cache.remove(&format!("{}{}", self.options.base_url, path));
+        }
+    }
+
+    fn fetch(&mut self, url: &str) -> Result<Response, Error> {
+        let mut backoff = self.options.backoff;
+        let mut retries_left = self.options.retries;
+        loop {
+            if let Some(last) = self.last_request {
+                thread::sleep(self.options.min_interval.saturating_sub(last.elapsed()));
+            }
+            self.last_request = Some(Instant::now());
+            let result = self.client.get(url).send().and_then(|resp| {
+                let status = resp.status().as_u16();
+                Ok(Response {
+                    status,
+                    body: resp.text()?,
+                })
+            });
+            let transient = match &result {
+                Ok(resp) => resp.status >= 500,
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+            if !transient || retries_left == 0 {
+                return Ok(result?);
+            }
+            eprintln!("Request to {} failed, retrying in {:?}", url, backoff);
+            thread::sleep(backoff);
+            backoff *= 2;
+            retries_left -= 1;
+        }
+    }
+}
+
+pub fn download_input(
+    downloader: &mut Downloader,
+    year: Year,
+    day: Day,
+) -> Result<Downloaded, Error> {
+    // We know when days unlock, so there is no need to ask the server before then
+    if SystemTime::now() < unlock_time(year, day) {
+        return Ok(Downloaded::NotYetReleased);
+    }
+    // Inputs never change, so a cached input is always good
+    let path = format!("/{}/day/{}/input", year, day.get());
+    let resp = downloader.get(&path, None)?;
+    if !resp.is_success() {
+        // The response will begin with this string if the day is not yet released
+        if resp
+            .body
+            .contains("Please don't repeatedly request this endpoint before it unlocks")
+        {
+            return Ok(Downloaded::NotYetReleased);
+        } else {
+            return Err(Error::Server(resp.body));
+        }
+    }
+    if let Err(reason) = validate_input(&resp.body) {
+        // Don't keep serving the bad response from the cache
+        downloader.forget(&path);
+        return Err(Error::InvalidInput(day, reason));
+    }
+    Ok(Downloaded::Data(resp.body))
+}
+
+// The server may respond successfully with something that is not an input, e.g. a login
+// page if the session has expired. Inputs are plain text, so anything HTML-like is rejected.
+fn validate_input(body: &str) -> Result<(), String> {
+    let start = body.trim_start().to_ascii_lowercase();
+    if body.trim().is_empty() {
+        Err("The input is empty".to_owned())
+    } else if body.contains("Puzzle inputs differ by user") {
+        Err("The server asks to log in, since puzzle inputs differ by user".to_owned())
+    } else if start.starts_with("<!doctype") || start.starts_with("<html") {
+        if body.contains("[Log In]") || body.contains("/auth/login") {
+            Err("The server responded with a login page".to_owned())
+        } else {
+            Err("The server responded with an HTML page".to_owned())
+        }
+    } else {
+        Ok(())
+    }
+}
+
+/// Download the puzzle page of the day, and extract the code blocks, which
+/// contain the examples.
+pub fn download_examples(
+    downloader: &mut Downloader,
+    year: Year,
+    day: Day,
+) -> Result<Vec<String>, Error> {
+    // The examples are in part 1 of the page, which never changes
+    let resp = downloader.get(&format!("/{}/day/{}", year, day.get()), None)?;
+    if !resp.is_success() {
+        return Err(Error::Server(resp.body));
+    }
+    Ok(extract_examples(&resp.body))
+}
+
+// Examples are the contents of <pre><code> blocks, which may contain markup like
+// <em> for emphasis, which we remove.
+fn extract_examples(html: &str) -> Vec<String> {
+    let block = regex::Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let tag = regex::Regex::new(r"<[^>]*>").unwrap();
+    block
+        .captures_iter(html)
+        .map(|cap| unescape_html(&tag.replace_all(&cap[1], "")))
+        .collect()
+}
+
+pub(crate) fn unescape_html(s: &str) -> String {
+    // &amp; must be last, such that e.g. "&amp;lt;" becomes "&lt;", not "<"
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_examples, unlock_time, validate_input, Downloader, Options};
+    use crate::{
+        cache::Cache,
+        test_util::{serve, TempDir},
+        Day, Year,
+    };
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_unlock_time() {
+        let unlock = |year, day| {
+            unlock_time(Year::new(year).unwrap(), Day::new(day).unwrap())
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        };
+        // 2024-12-01T05:00:00Z, in a leap year
+        assert_eq!(unlock(2024, 1), 1733029200);
+        // 2023-12-25T05:00:00Z
+        assert_eq!(unlock(2023, 25), 1703480400);
+        // 2100-12-10T05:00:00Z, where 2100 is not a leap year, despite being divisible by 4
+        assert_eq!(unlock(2100, 10), 4132098000);
+        // 2015-12-01T05:00:00Z
+        assert_eq!(unlock(2015, 1), 1448946000);
+    }
+
+    #[test]
+    fn test_extract_examples() {
+        let html = "<article><p>For example:</p>
+<pre><code>3   4
+4   <em>3</em>
+</code></pre>
+<p>Then <code>x</code></p><pre><code>a -&gt; b &amp;&amp; c
+</code></pre></article>";
+        assert_eq!(
+            extract_examples(html),
+            vec!["3   4\n4   3\n".to_owned(), "a -> b && c\n".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_validate_input() {
+        assert!(validate_input("3   4\n4   3\n").is_ok());
+        // Inputs may contain angle brackets, as long as they are not HTML
+        assert!(validate_input("<<>>\n<>\n").is_ok());
+        assert!(validate_input("").is_err());
+        assert!(validate_input("  \n").is_err());
+        assert!(validate_input(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        )
+        .is_err());
+        assert!(validate_input(
+            "<!DOCTYPE html>\n<html lang=\"en-us\"><body><a href=\"/2024/auth/login\">[Log In]</a></body></html>"
+        )
+        .is_err());
+        assert!(validate_input("\n<html><body>Internal error</body></html>").is_err());
+    }
+
+    #[test]
+    fn test_downloader() {
+        // The server fails once, then succeeds, then shuts down
+        let (base_url, server) = serve(vec![
+            ("503 Service Unavailable", "Oops"),
+            ("200 OK", "1 2\n"),
+        ]);
+        let cache_dir = TempDir::new("download");
+        let options = Options {
+            base_url,
+            min_interval: Duration::ZERO,
+            backoff: Duration::from_millis(1),
+            cache: Some(Cache::new(cache_dir.path().to_owned())),
+            ..Default::default()
+        };
+        let client = reqwest::blocking::Client::builder()
+            .user_agent("test-agent")
+            .build()
+            .unwrap();
+        let mut downloader = Downloader::new(client, options);
+        let resp = downloader.get("/2024/day/1/input", None).unwrap();
+        assert_eq!((resp.status, resp.body.as_str()), (200, "1 2\n"));
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].starts_with("GET /2024/day/1/input "));
+        assert!(requests[1].contains("user-agent: test-agent\r\n"));
+        // The server is gone now, so this must come from the cache
+        let resp = downloader.get("/2024/day/1/input", None).unwrap();
+        assert_eq!((resp.status, resp.body.as_str()), (200, "1 2\n"));
+    }
+}
@@ -0,0 +1,183 @@
+use std::{fmt::Display, path::PathBuf};
+
+use crate::{submit::Outcome, Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    /// A day was not an integer in 1-25
+    InvalidDay(String),
+    /// A year was not an integer from 2015
+    InvalidYear(String),
+    /// Days were given explicitly while --all was also set
+    ConflictingDays,
+    /// A path that must be a directory is not one
+    NotADirectory(PathBuf),
+    /// An I/O operation failed. The string describes what we tried to do.
+    Io(String, std::io::Error),
+    /// The answers file could not be parsed
+    InvalidAnswersFile(String),
+    /// The benchmark baseline file could not be parsed
+    InvalidBaselineFile(String),
+    /// A new day could not be added to the source tree
+    Scaffold(String),
+    /// The day has no visualization
+    NoVisualization(Year, Day),
+    /// There is no cache directory, but the cache is needed for the reason given
+    NoCache(&'static str),
+    /// The session key is not 128-character hex
+    InvalidSessionKey,
+    /// No session key was given, and we could not prompt for it
+    MissingSessionKey,
+    /// The config file could not be parsed
+    InvalidConfigFile(String),
+    /// The request failed, e.g. because the network is down
+    Http(reqwest::Error),
+    /// The server responded, but not with what we expected
+    Server(String),
+    /// The request was not made, because the same request failed too recently. The
+    /// string describes the request.
+    RecentlyFailed(String),
+    /// The server responded successfully, but not with a puzzle input
+    InvalidInput(Day, String),
+    /// An explicitly requested day is not yet released
+    NotYetReleased(Day),
+    /// Some computed answers did not match the answers file
+    WrongAnswers(usize),
+    /// Some days panicked or timed out when solving
+    FailedDays(usize),
+    /// The AoC server did not accept a submitted answer
+    AnswerRejected(Outcome),
+}
+
+impl Error {
+    /// The exit code of the process, if it exits with this error. Codes are distinct
+    /// per category, such that scripts can distinguish e.g. unreleased days from
+    /// network errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::InvalidDay(_)
+            | Self::InvalidYear(_)
+            | Self::ConflictingDays
+            | Self::InvalidSessionKey
+            | Self::MissingSessionKey
+            | Self::NoVisualization(..) => 2,
+            Self::NotADirectory(_)
+            | Self::Io(..)
+            | Self::InvalidAnswersFile(_)
+            | Self::InvalidBaselineFile(_)
+            | Self::InvalidConfigFile(_)
+            | Self::NoCache(_)
+            | Self::Scaffold(_) => 3,
+            Self::Http(_) | Self::Server(_) | Self::RecentlyFailed(_) | Self::InvalidInput(..) => 4,
+            Self::NotYetReleased(_) => 5,
+            Self::WrongAnswers(_) | Self::AnswerRejected(_) => 6,
+            Self::FailedDays(_) => 7,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDay(s) => write!(f, "Day must be an integer in 1-25, got \"{}\"", s),
+            Self::InvalidYear(s) => write!(f, "Year must be an integer from 2015, got \"{}\"", s),
+            Self::ConflictingDays => write!(f, "If --all is set, days cannot be passed explicitly"),
+            Self::NotADirectory(p) => write!(f, "{:?} is not an existing directory", p),
+            Self::Io(msg, e) => write!(f, "{}: {}", msg, e),
+            Self::InvalidAnswersFile(msg) => write!(f, "Invalid answers file: {}", msg),
+            Self::InvalidBaselineFile(msg) => write!(f, "Invalid baseline file: {}", msg),
+            Self::InvalidConfigFile(msg) => write!(f, "Invalid config file: {}", msg),
+            Self::Scaffold(msg) => write!(f, "Could not add new day: {}", msg),
+            Self::NoCache(reason) => write!(
+                f,
+                "{}, which is tracked in the cache, but there is no cache directory. \
+                Set XDG_CACHE_HOME or HOME.",
+                reason
+            ),
+            Self::NoVisualization(year, day) => {
+                write!(f, "Day {} of {} has no visualization", day, year)
+            }
+            Self::InvalidSessionKey => {
+                write!(f, "Session key not a 128-character hexadecimal string")
+            }
+            Self::MissingSessionKey => write!(
+                f,
+                "No session key found. Pass --session-key, set the {} environment variable, \
+                or set session_key in the config file",
+                crate::session::ENV_VAR
+            ),
+            Self::Http(e) => write!(f, "Error when processing request: {}", e),
+            Self::Server(text) => write!(f, "Unexpected response from server:\n{}", text),
+            Self::RecentlyFailed(what) => write!(
+                f,
+                "{} failed less than 15 minutes ago, and is not retried until then",
+                what
+            ),
+            Self::InvalidInput(day, reason) => write!(
+                f,
+                "Downloaded data for day {} is not a puzzle input: {}. \
+                The session key may have expired.",
+                day, reason
+            ),
+            Self::NotYetReleased(day) => write!(
+                f,
+                "Explicitly requested day {}, but this day is not yet released.",
+                day
+            ),
+            Self::WrongAnswers(n) => {
+                write!(f, "{} answer(s) did not match the answers file", n)
+            }
+            Self::AnswerRejected(outcome) => write!(f, "{}", outcome),
+            Self::FailedDays(n) => write!(f, "{} day(s) panicked or timed out", n),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, e) => Some(e),
+            Self::Http(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Http(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use crate::{submit::Outcome, Day};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_exit_codes() {
+        let day = Day::new(1).unwrap();
+        let io = || std::io::Error::from(std::io::ErrorKind::NotFound);
+        let cases = [
+            (Error::InvalidDay("26".to_owned()), 2),
+            (Error::ConflictingDays, 2),
+            (Error::InvalidSessionKey, 2),
+            (Error::NotADirectory(PathBuf::from("data")), 3),
+            (Error::Io("Could not read".to_owned(), io()), 3),
+            (Error::InvalidAnswersFile(String::new()), 3),
+            (Error::InvalidBaselineFile(String::new()), 3),
+            (Error::NoCache("Because"), 3),
+            (Error::Server("Login".to_owned()), 4),
+            (Error::RecentlyFailed("Fetching".to_owned()), 4),
+            (Error::InvalidInput(day, "HTML".to_owned()), 4),
+            (Error::NotYetReleased(day), 5),
+            (Error::WrongAnswers(1), 6),
+            (Error::AnswerRejected(Outcome::TooLow), 6),
+            (Error::FailedDays(1), 7),
+        ];
+        for (error, code) in cases {
+            assert_eq!(error.exit_code(), code, "{:?}", error);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// A position in a grid. Row 0 is the top row, column 0 the leftmost column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Pos { row, col }
+    }
+}
+
+/// A step between positions, as (rows, columns). Negative rows are up.
+pub type Offset = (isize, isize);
+
+/// The eight offsets to the neighbours of a position, including diagonals, clockwise
+/// from straight up
+pub const NEIGHBOURS_8: [Offset; 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// The four diagonal offsets, clockwise from up and right
+pub const DIAGONALS: [Offset; 4] = [(-1, 1), (1, 1), (1, -1), (-1, -1)];
+
+/// One of the four straight directions, as seen on a map with up being north
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up = 0,
+    Right = 1,
+    Down = 2,
+    Left = 3,
+}
+
+impl Direction {
+    /// All directions, clockwise from up
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn offset(self) -> Offset {
+        match self {
+            Self::Up => (-1, 0),
+            Self::Right => (0, 1),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The direction of an arrow like `^` or `>`, as used on maps in puzzle inputs
+    pub fn from_arrow(b: u8) -> Option<Self> {
+        match b {
+            b'^' => Some(Self::Up),
+            b'>' => Some(Self::Right),
+            b'v' => Some(Self::Down),
+            b'<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+}
+
+/// A rectangular grid, like the maps in many puzzle inputs. The cells are stored in a
+/// single vector, row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parse a grid with a line per row, converting each byte to a cell with `f`.
+    ///
+    /// # Panics
+    /// If the text is empty, or the lines are not all the same length
+    pub fn parse(s: &str, mut f: impl FnMut(u8) -> T) -> Self {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(s.len());
+        let mut height = 0;
+        for (row, line) in s.lines().enumerate() {
+            let width = *width.get_or_insert(line.len());
+            if line.len() != width {
+                panic!(
+                    "Line {} of grid has length {}, but line 1 has length {}",
+                    row + 1,
+                    line.len(),
+                    width
+                );
+            }
+            cells.extend(line.bytes().map(&mut f));
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Grid {
+                width,
+                height,
+                cells,
+            },
+            _ => panic!("Grid is empty"),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.row * self.width + pos.col])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.row * self.width + pos.col])
+    }
+
+    /// The position one step from `pos`, if it is in the grid
+    pub fn step(&self, pos: Pos, (dr, dc): Offset) -> Option<Pos> {
+        let next = Pos::new(
+            pos.row.checked_add_signed(dr)?,
+            pos.col.checked_add_signed(dc)?,
+        );
+        self.contains(next).then_some(next)
+    }
+
+    /// The position one step from `pos`, where stepping off an edge enters on the
+    /// opposite edge
+    pub fn step_wrapping(&self, pos: Pos, (dr, dc): Offset) -> Pos {
+        let wrap = |i: usize, d: isize, n: usize| (i as isize + d).rem_euclid(n as isize) as usize;
+        Pos::new(
+            wrap(pos.row, dr, self.height),
+            wrap(pos.col, dc, self.width),
+        )
+    }
+
+    /// The neighbours of the position above, below, left and right of it that are in the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d.offset()))
+    }
+
+    /// The neighbours of the position, including diagonals, that are in the grid
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_8
+            .into_iter()
+            .filter_map(move |offset| self.step(pos, offset))
+    }
+
+    /// The positions reached by repeatedly stepping from `pos`, until leaving the grid.
+    /// `pos` itself is not included.
+    pub fn ray(&self, pos: Pos, offset: Offset) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, offset), move |&p| self.step(p, offset))
+    }
+
+    /// All positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Pos::new(i / width, i % width))
+    }
+
+    /// All positions and their cells, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, with a cell that matches the predicate
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        let i = self.cells.iter().position(&mut predicate)?;
+        Some(Pos::new(i / self.width, i % self.width))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width)
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// A grid of the same size, with each cell converted by `f`
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "{:?} is outside the {}x{} grid",
+                pos, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside the {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+// Cells are shown as a character each, so e.g. a grid parsed from bytes prints as the input
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(|&c| c.into()).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Grid, Pos};
+
+    #[test]
+    fn test_grid() {
+        let mut grid = Grid::parse("ab.\n.#c\n", |b| b);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pos::new(1, 2)], b'c');
+        assert_eq!(grid.get(Pos::new(2, 0)), None);
+        assert_eq!(grid.position(|&b| b == b'#'), Some(Pos::new(1, 1)));
+
+        let corner = Pos::new(0, 0);
+        assert_eq!(grid.step(corner, Direction::Up.offset()), None);
+        assert_eq!(
+            grid.step_wrapping(corner, Direction::Up.offset()),
+            Pos::new(1, 0)
+        );
+        assert_eq!(grid.step_wrapping(corner, (-1, -1)), Pos::new(1, 2));
+        assert_eq!(
+            grid.neighbours4(corner).collect::<Vec<_>>(),
+            [Pos::new(0, 1), Pos::new(1, 0)]
+        );
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 5);
+        let ray: Vec<_> = grid.ray(corner, (0, 1)).map(|p| grid[p]).collect();
+        assert_eq!(ray, b"b.");
+
+        grid[Pos::new(0, 2)] = b'#';
+        assert_eq!(grid.to_string(), "ab#\n.#c\n");
+        let walls = grid.map(|&b| b == b'#');
+        assert_eq!(walls.iter().filter(|(_, &w)| w).count(), 2);
+
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+    }
+
+    #[test]
+    #[should_panic(expected = "Line 2 of grid has length 2, but line 1 has length 3")]
+    fn test_ragged_grid() {
+        Grid::parse("abc\nde\n", |b| b);
+    }
+}
@@ -0,0 +1,338 @@
+use std::{collections::BTreeMap, fmt::Write, time::Duration};
+
+use serde_json::Value;
+
+use crate::{
+    download::{unlock_time, Downloader, FAILED},
+    error::Error,
+    Day, Year,
+};
+
+/// The AoC maintainers ask that private leaderboards are fetched at most once per 15 minutes
+pub const MIN_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+pub struct Member {
+    pub id: u64,
+    /// Members who have not set a name are anonymous
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    /// Unix timestamps of when each part of each day was completed
+    pub completed: BTreeMap<Day, [Option<u64>; 2]>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+}
+
+/// A private leaderboard, with members sorted by score, best first
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub members: Vec<Member>,
+}
+
+/// Fetch the private leaderboard with the given ID. A cached copy is used if it is
+/// younger than `MIN_REFRESH`, so this may be called as often as you like. Failed attempts
+/// are cached too, and not retried until they are `MIN_REFRESH` old. Without a cache,
+/// there is no way to know when it was last fetched, so it is not fetched at all.
+pub fn fetch(downloader: &mut Downloader, year: Year, id: u64) -> Result<Leaderboard, Error> {
+    if !downloader.has_cache() {
+        return Err(Error::NoCache(
+            "Private leaderboards may only be fetched once per 15 minutes",
+        ));
+    }
+    let path = format!("/{}/leaderboard/private/view/{}.json", year, id);
+    let resp = downloader.get(&path, Some(MIN_REFRESH))?;
+    if resp.status == FAILED {
+        return Err(Error::RecentlyFailed(format!(
+            "Fetching leaderboard {}",
+            id
+        )));
+    }
+    if !resp.is_success() {
+        downloader.cache_failure(&path);
+        return Err(Error::Server(resp.body));
+    }
+    // If we are not allowed to see the leaderboard, the server redirects to an HTML page
+    parse(&resp.body).map_err(|e| {
+        downloader.cache_failure(&path);
+        Error::Server(format!(
+            "Could not parse leaderboard {}: {}. Check the ID, and that you are a member.",
+            id, e
+        ))
+    })
+}
+
+/// Parse the JSON of a private leaderboard, of the form:
+///
+/// ```json
+/// {"owner_id": 1, "event": "2024", "members": {"1": {"id": 1, "name": "Alice",
+///  "stars": 1, "local_score": 5, "completion_day_level": {"1": {"1": {"get_star_ts": 1733029500}}}}}}
+/// ```
+pub fn parse(json: &str) -> Result<Leaderboard, String> {
+    let root: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let integer = |value: &Value, key: &str| {
+        // Older leaderboards have some numbers as strings
+        match &value[key] {
+            Value::Number(n) => n.as_u64(),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| format!("Missing or invalid \"{}\"", key))
+    };
+    let members = root["members"]
+        .as_object()
+        .ok_or("Missing \"members\"")?
+        .values()
+        .map(|member| {
+            let mut completed = BTreeMap::new();
+            if let Some(days) = member["completion_day_level"].as_object() {
+                for (day, parts) in days {
+                    let day: Day = day
+                        .parse()
+                        .map_err(|_| format!("Invalid day \"{}\"", day))?;
+                    let mut timestamps = [None, None];
+                    for (i, timestamp) in timestamps.iter_mut().enumerate() {
+                        let part = &parts[(i + 1).to_string()];
+                        if !part.is_null() {
+                            *timestamp = Some(integer(part, "get_star_ts")?);
+                        }
+                    }
+                    completed.insert(day, timestamps);
+                }
+            }
+            Ok(Member {
+                id: integer(member, "id")?,
+                name: member["name"].as_str().map(str::to_owned),
+                local_score: integer(member, "local_score")?,
+                stars: integer(member, "stars")?,
+                completed,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let mut leaderboard = Leaderboard {
+        owner_id: integer(&root, "owner_id")?,
+        members,
+    };
+    leaderboard.members.sort_by(|a, b| {
+        (b.local_score, b.stars)
+            .cmp(&(a.local_score, a.stars))
+            .then_with(|| a.display_name().cmp(&b.display_name()))
+    });
+    Ok(leaderboard)
+}
+
+/// A table with the score and stars of each member. A day with both parts completed is
+/// shown as `*`, one with only part 1 completed as `+`.
+pub fn render(leaderboard: &Leaderboard, year: Year) -> String {
+    let n_days = year.n_days();
+    let score_width = leaderboard
+        .members
+        .iter()
+        .map(|m| m.local_score.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let rank_width = leaderboard.members.len().to_string().len() + 1;
+    // The day numbers are written vertically, with the tens above the ones
+    let indent = " ".repeat(rank_width + score_width + 2);
+    let mut s = String::new();
+    let tens: String = (1..=n_days)
+        .map(|d| {
+            if d >= 10 {
+                (b'0' + d / 10) as char
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    let ones: String = (1..=n_days).map(|d| (b'0' + d % 10) as char).collect();
+    writeln!(s, "{}{}", indent, tens.trim_end()).unwrap();
+    writeln!(s, "{}{}", indent, ones).unwrap();
+    for (i, member) in leaderboard.members.iter().enumerate() {
+        let stars: String = (1..=n_days)
+            .map(|d| {
+                let day = Day::new(d).expect("Days are in 1..=25");
+                match member.completed.get(&day) {
+                    Some([_, Some(_)]) => '*',
+                    Some([Some(_), None]) => '+',
+                    _ => '.',
+                }
+            })
+            .collect();
+        writeln!(
+            s,
+            "{:>rank_width$} {:>score_width$} {} {}",
+            format!("{})", i + 1),
+            member.local_score,
+            stars,
+            member.display_name(),
+        )
+        .unwrap();
+    }
+    s
+}
+
+/// A table of when each member completed each part of the day, counted from when the
+/// day unlocked, and the time between the parts
+pub fn render_day(leaderboard: &Leaderboard, year: Year, day: Day) -> String {
+    let unlock = unlock_time(year, day)
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let since_unlock = |ts: Option<u64>| ts.map(|ts| ts.saturating_sub(unlock));
+    let mut rows: Vec<(String, Option<u64>, Option<u64>)> = leaderboard
+        .members
+        .iter()
+        .filter_map(|m| {
+            let [one, two] = m.completed.get(&day)?;
+            Some((m.display_name(), since_unlock(*one), since_unlock(*two)))
+        })
+        .collect();
+    // Fastest first. Members with only part 1 go after those with both.
+    rows.sort_by_key(|&(_, one, two)| (two.is_none(), two, one));
+    let name_width = rows.iter().map(|r| r.0.chars().count()).max().unwrap_or(0);
+    let mut s = format!("Day {}\n", day);
+    if rows.is_empty() {
+        s.push_str("No stars yet\n");
+        return s;
+    }
+    writeln!(
+        s,
+        "{:name_width$}  {:>10}  {:>10}  {:>10}",
+        "", "Part 1", "Part 2", "Delta"
+    )
+    .unwrap();
+    for (name, one, two) in rows {
+        let delta = one.zip(two).map(|(one, two)| two.saturating_sub(one));
+        writeln!(
+            s,
+            "{:name_width$}  {:>10}  {:>10}  {:>10}",
+            name,
+            hms(one),
+            hms(two),
+            hms(delta)
+        )
+        .unwrap();
+    }
+    s
+}
+
+// Hours can exceed 24, as people may complete a day long after it unlocked
+fn hms(secs: Option<u64>) -> String {
+    match secs {
+        None => "-".to_owned(),
+        Some(secs) => format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fetch, parse, render, render_day};
+    use crate::{
+        cache::Cache,
+        download::{Downloader, Options},
+        error::Error,
+        test_util::{serve, TempDir},
+        Day, Year,
+    };
+    use std::time::Duration;
+
+    static LEADERBOARD: &str = include_str!("../fixtures/leaderboard.json");
+    static EMPTY: &str = include_str!("../fixtures/leaderboard_empty.json");
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = parse(LEADERBOARD).unwrap();
+        assert_eq!(leaderboard.owner_id, 1001);
+        let names: Vec<_> = leaderboard
+            .members
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        assert_eq!(names, ["Alice", "(anonymous user #2002)", "Carol"]);
+        let anonymous = &leaderboard.members[1];
+        assert_eq!((anonymous.local_score, anonymous.stars), (11, 3));
+        let day = |n| Day::new(n).unwrap();
+        assert_eq!(
+            anonymous.completed[&day(1)],
+            [Some(1733029800), Some(1733040600)]
+        );
+        assert_eq!(anonymous.completed[&day(2)], [Some(1733122800), None]);
+        assert!(leaderboard.members[2].completed.is_empty());
+
+        let empty = parse(EMPTY).unwrap();
+        assert_eq!(empty.members.len(), 1);
+        assert!(parse("<!DOCTYPE html>").is_err());
+        assert!(parse("{\"members\": {\"1\": {\"id\": 1}}}").is_err());
+    }
+
+    #[test]
+    fn test_fetch_without_cache() {
+        // Nothing listens here, so this fails with an HTTP error if a request is made
+        let options = Options {
+            base_url: "http://127.0.0.1:1".to_owned(),
+            retries: 0,
+            ..Default::default()
+        };
+        let mut downloader = Downloader::new(reqwest::blocking::Client::new(), options);
+        assert!(matches!(
+            fetch(&mut downloader, Year::default(), 1),
+            Err(Error::NoCache(_))
+        ));
+    }
+
+    #[test]
+    fn test_fetch_after_failure() {
+        // The server only answers once, so a second request would fail with an HTTP error
+        let (base_url, server) = serve(vec![("200 OK", "<!DOCTYPE html>")]);
+        let cache_dir = TempDir::new("leaderboard");
+        let options = Options {
+            base_url,
+            min_interval: Duration::ZERO,
+            cache: Some(Cache::new(cache_dir.path().to_owned())),
+            ..Default::default()
+        };
+        let mut downloader = Downloader::new(reqwest::blocking::Client::new(), options);
+        assert!(matches!(
+            fetch(&mut downloader, Year::default(), 1),
+            Err(Error::Server(_))
+        ));
+        assert_eq!(server.join().unwrap().len(), 1);
+        assert!(matches!(
+            fetch(&mut downloader, Year::default(), 1),
+            Err(Error::RecentlyFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard = parse(LEADERBOARD).unwrap();
+        let year = Year::default();
+        assert_eq!(
+            render(&leaderboard, year),
+            "               1111111111222222
+      1234567890123456789012345
+1) 14 **....................... Alice
+2) 11 *+....................... (anonymous user #2002)
+3)  0 ......................... Carol
+"
+        );
+        assert_eq!(
+            render_day(&leaderboard, year, Day::new(2).unwrap()),
+            "Day 02
+                            Part 1      Part 2       Delta
+Alice                      0:10:00     0:15:00     0:05:00
+(anonymous user #2002)     2:00:00           -           -
+"
+        );
+        assert_eq!(
+            render_day(&parse(EMPTY).unwrap(), year, Day::new(1).unwrap()),
+            "Day 01\nNo stars yet\n"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod days;
+pub mod description;
+pub mod download;
+pub mod error;
+pub mod grid;
+pub mod leaderboard;
+pub mod parse;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod session;
+pub mod submit;
+pub mod visualize;
+
+#[cfg(test)]
+mod test_util;
+
+use bench::{Stage, Stats};
+use error::Error;
+use visualize::Visualizer;
+
+// This struct is used when I've implemented part 1 of a day, but not part 2.
+// I still want to be able to print part 1.
+#[allow(unused)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnimplementedPartTwo;
+
+impl Display for UnimplementedPartTwo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Not yet implemented")
+    }
+}
+
+/// The answer to a single part of a day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Solved(String),
+    Unimplemented,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solved(s) => write!(f, "{}", s),
+            Self::Unimplemented => write!(f, "{}", UnimplementedPartTwo),
+        }
+    }
+}
+
+/// Types that the day solvers can return as answers
+pub trait IntoAnswer {
+    /// Whether the part returning this type is implemented
+    const IMPLEMENTED: bool = true;
+
+    fn into_answer(self) -> Answer;
+}
+
+impl IntoAnswer for UnimplementedPartTwo {
+    const IMPLEMENTED: bool = false;
+
+    fn into_answer(self) -> Answer {
+        Answer::Unimplemented
+    }
+}
+
+macro_rules! impl_into_answer {
+    ($($t:ty),*) => {
+        $(
+            impl IntoAnswer for $t {
+                fn into_answer(self) -> Answer {
+                    Answer::Solved(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_into_answer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, String);
+
+/// The interface of a day. The input is first parsed into an intermediate, from which
+/// each of the two parts can be solved separately.
+pub trait Solution {
+    type Parsed;
+    type Answer1: IntoAnswer;
+    type Answer2: IntoAnswer;
+
+    fn parse(input: &str) -> Self::Parsed;
+    fn part_one(parsed: &Self::Parsed) -> Self::Answer1;
+    fn part_two(parsed: &Self::Parsed) -> Self::Answer2;
+
+    /// Parse, and solve both parts
+    fn solve(input: &str) -> (Self::Answer1, Self::Answer2) {
+        let parsed = Self::parse(input);
+        (Self::part_one(&parsed), Self::part_two(&parsed))
+    }
+}
+
+/// A parsed input of some day, with the concrete type erased.
+/// Days may be solved on other threads, so it must be `Send + Sync`.
+pub trait Puzzle: Send + Sync {
+    fn part_one(&self) -> Answer;
+    fn part_two(&self) -> Answer;
+
+    fn part(&self, part: u8) -> Answer {
+        match part {
+            1 => self.part_one(),
+            2 => self.part_two(),
+            _ => panic!("Part must be 1 or 2"),
+        }
+    }
+
+    /// Time a stage of the day for the input this was parsed from. Only the day's own
+    /// functions are timed, not converting their answers or dropping their results.
+    fn bench(&self, input: &str, stage: Stage, options: &bench::Options) -> Stats;
+}
+
+struct Parsed<S: Solution>(S::Parsed);
+
+impl<S: Solution> Puzzle for Parsed<S>
+where
+    S::Parsed: Send + Sync,
+{
+    fn part_one(&self) -> Answer {
+        S::part_one(&self.0).into_answer()
+    }
+
+    fn part_two(&self) -> Answer {
+        S::part_two(&self.0).into_answer()
+    }
+
+    fn bench(&self, input: &str, stage: Stage, options: &bench::Options) -> Stats {
+        match stage {
+            Stage::Parse => bench::bench(|| S::parse(black_box(input)), options),
+            Stage::Part(1) => bench::bench(|| S::part_one(black_box(&self.0)), options),
+            Stage::Part(2) => bench::bench(|| S::part_two(black_box(&self.0)), options),
+            Stage::Part(_) => panic!("Part must be 1 or 2"),
+        }
+    }
+}
+
+/// Parses the input of a day, returning a puzzle from which each part can be solved
+pub type Solver = fn(&str) -> Box<dyn Puzzle>;
+
+fn parse_boxed<S: Solution + 'static>(s: &str) -> Box<dyn Puzzle>
+where
+    S::Parsed: Send + Sync,
+{
+    Box::new(Parsed::<S>(S::parse(s)))
+}
+
+/// An implemented day, as registered in the `days` module
+pub struct Registration {
+    pub year: Year,
+    pub day: Day,
+    pub solver: Solver,
+    /// Whether part 1 and part 2 are implemented
+    pub parts: [bool; 2],
+    /// Animates solving the day, if the day has a visualization
+    pub visualizer: Option<Visualizer>,
+}
+
+impl Registration {
+    const fn new<S: Solution + 'static>(year: u16, day: u8) -> Self
+    where
+        S::Parsed: Send + Sync,
+    {
+        assert!(year >= FIRST_YEAR);
+        assert!(day >= 1 && day <= 25);
+        Registration {
+            year: Year(year),
+            day: Day(day),
+            solver: parse_boxed::<S>,
+            parts: [S::Answer1::IMPLEMENTED, S::Answer2::IMPLEMENTED],
+            visualizer: None,
+        }
+    }
+
+    const fn with_visualizer(mut self, visualizer: Visualizer) -> Self {
+        self.visualizer = Some(visualizer);
+        self
+    }
+}
+
+/// All implemented days, sorted by year, then day
+pub fn registry() -> impl Iterator<Item = &'static Registration> {
+    days::REGISTRY.iter().flat_map(|year| year.iter())
+}
+
+/// Get the registration of the given day, if the day is implemented
+pub fn get_registration(year: Year, day: Day) -> Option<&'static Registration> {
+    registry().find(|r| r.year == year && r.day == day)
+}
+
+/// Get the solver of the given day, if the day is implemented
+pub fn get_solver(year: Year, day: Day) -> Option<Solver> {
+    get_registration(year, day).map(|r| r.solver)
+}
+
+const FIRST_YEAR: u16 = 2015;
+
+/// A year of Advent of Code, from 2015 onwards
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    pub fn new(u: u16) -> Result<Self, Error> {
+        if u < FIRST_YEAR {
+            return Err(Error::InvalidYear(u.to_string()));
+        };
+        Ok(Year(u))
+    }
+
+    pub fn get(self) -> u16 {
+        self.0
+    }
+
+    /// Number of days in the year. From 2025, AoC only has 12 days.
+    pub fn n_days(self) -> u8 {
+        if self.0 >= 2025 {
+            12
+        } else {
+            25
+        }
+    }
+}
+
+impl Default for Year {
+    fn default() -> Self {
+        Year(2024)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let u: u16 = s
+            .parse::<_>()
+            .map_err(|_| Error::InvalidYear(s.to_owned()))?;
+        Year::new(u)
+    }
+}
+
+/// A day in 1-25
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct Day(u8);
+
+impl Day {
+    pub fn new(u: u8) -> Result<Self, Error> {
+        if !(1..=25).contains(&u) {
+            return Err(Error::InvalidDay(u.to_string()));
+        };
+        Ok(Day(u))
+    }
+
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+// Days are zero-padded, such that they sort and line up nicely
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}", self.0)
+    }
+}
+
+impl FromStr for Day {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let u: u8 = s
+            .parse::<_>()
+            .map_err(|_| Error::InvalidDay(s.to_owned()))?;
+        Day::new(u)
+    }
+}
+
+pub enum Days {
+    All,
+    Explicit(Vec<Day>),
+}
+
+impl Days {
+    pub fn new(days: Option<Vec<Day>>, all: bool) -> Result<Self, Error> {
+        if all && days.is_some() {
+            return Err(Error::ConflictingDays);
+        } else if !all && days.is_none() {
+            return Ok(Days::Explicit(Vec::new()));
+        }
+        Ok(match days {
+            None => Days::All,
+            Some(mut v) => {
+                v.sort_unstable();
+                v.dedup();
+                Days::Explicit(v)
+            }
+        })
+    }
+
+    pub fn as_vec(&self, year: Year) -> Vec<Day> {
+        match self {
+            Self::All => (1..=year.n_days()).map(Day).collect(),
+            Self::Explicit(v) => v.clone(),
+        }
+    }
+}
+
+/// Directory with the data of the given year, e.g. "data/2024"
+pub fn year_dir(data_dir: &Path, year: Year) -> PathBuf {
+    data_dir.join(year.to_string())
+}
+
+/// Path of the input file of the day in the data directory of a year, e.g. "data/2024/day01.txt"
+pub fn input_path(data_dir: &Path, day: Day) -> PathBuf {
+    data_dir.join(format!("day{}.txt", day))
+}
+
+/// Path of the n'th example file of the day, e.g. "data/day01.example.txt" for the
+/// first example, and "data/day01.example2.txt" for the second
+pub fn example_path(data_dir: &Path, day: Day, n: usize) -> PathBuf {
+    if n == 1 {
+        data_dir.join(format!("day{}.example.txt", day))
+    } else {
+        data_dir.join(format!("day{}.example{}.txt", day, n))
+    }
+}
+
+/// Paths of all existing example files of the day, in order
+pub fn example_paths(data_dir: &Path, day: Day) -> Vec<PathBuf> {
+    (1..)
+        .map(|n| example_path(data_dir, day, n))
+        .take_while(|p| p.is_file())
+        .collect()
+}
+
+/// Path of the puzzle description of the day, e.g. "data/2024/day01.md"
+pub fn description_path(data_dir: &Path, day: Day) -> PathBuf {
+    data_dir.join(format!("day{}.md", day))
+}
+
+pub fn load_input(data_dir: &Path, day: Day) -> Result<String, Error> {
+    read_file(&input_path(data_dir, day))
+}
+
+/// Write the file by writing a temporary file next to it, then renaming it. Like this,
+/// the file is never left half-written, even if the program is interrupted.
+pub fn write_file_atomic(path: &Path, data: &str) -> Result<(), Error> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    std::fs::write(&temp, data).map_err(|e| {
+        Error::Io(
+            format!("Error when writing data to file at \"{:?}\"", temp),
+            e,
+        )
+    })?;
+    std::fs::rename(&temp, path).map_err(|e| {
+        Error::Io(
+            format!("Could not move \"{:?}\" to \"{:?}\"", temp, path),
+            e,
+        )
+    })
+}
+
+pub fn read_file(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|e| {
+        Error::Io(
+            format!("Could not read file \"{:?}\" into UTF-8 string", path),
+            e,
+        )
+    })
+}
+
+/// Parse a duration like "10s", "1.5m", "500ms" or "2h". A bare number is in seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("Invalid duration \"{}\"", s))?;
+    let secs = match unit.trim() {
+        "" | "s" => number,
+        "ms" => number / 1000.0,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => {
+            return Err(format!(
+                "Invalid unit of duration \"{}\", use ms, s, m or h",
+                s
+            ))
+        }
+    };
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("Invalid duration \"{}\"", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_duration;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("1.5m"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(7200)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("5 days").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("NaN").is_err());
+        assert!(parse_duration(&"9".repeat(30)).is_err());
+    }
+}
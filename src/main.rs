@@ -1,213 +1,512 @@
 use clap::{Parser, Subcommand};
-use reqwest::blocking::Client;
 
 use std::{
-    cell::OnceCell,
-    fmt::Display,
-    fs::File,
-    io::Write,
+    collections::HashMap,
+    io::{IsTerminal, Write},
     path::{Path, PathBuf},
-    str::FromStr,
-    time::Instant,
+    time::{Duration, SystemTime},
 };
 
-mod days;
-
-fn exit_with(s: &str) -> ! {
-    eprintln!("{}", s);
-    std::process::exit(1)
-}
-
-// This struct is used when I've implemented part 1 of a day, but not part 2.
-// I still want to be able to print part 1.
-#[allow(unused)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct UnimplementedPartTwo;
-
-impl Display for UnimplementedPartTwo {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Not yet implemented")
-    }
-}
-
-type Solver = Box<dyn Fn(&str) -> (Box<dyn Display>, Box<dyn Display>)>;
-
-fn box_solver<F, A, B>(f: F) -> Option<Solver>
-where
-    A: Display + 'static,
-    B: Display + 'static,
-    F: Fn(&str) -> (A, B) + 'static,
-{
-    Some(Box::new(move |s| {
-        let (a, b) = f(s);
-        (Box::new(a), Box::new(b))
-    }))
-}
-
-fn get_solver(day: Day) -> Option<Solver> {
-    match day.0 {
-        1 => box_solver(days::day01::solve),
-        2 => box_solver(days::day02::solve),
-        3 => box_solver(days::day03::solve),
-        4 => box_solver(days::day04::solve),
-        5 => box_solver(days::day05::solve),
-        6 => box_solver(days::day06::solve),
-        7 => box_solver(days::day07::solve),
-        _ => None,
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
-struct Day(u8);
-
-impl Day {
-    fn new_or_exit(u: u8) -> Self {
-        if !(1..=25).contains(&u) {
-            exit_with(&format!("Day {} must be in 1-25", u))
-        };
-        Day(u)
-    }
-}
-
-impl FromStr for Day {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let u: u8 = s
-            .parse::<_>()
-            .map_err(|_| format!("Could not parse as day integer: {}", s))?;
-        Ok(Day::new_or_exit(u))
-    }
-}
-
-enum Days {
-    All,
-    Explicit(Vec<Day>),
-}
-
-impl Days {
-    fn new(days: Option<Vec<Day>>, all: bool) -> Self {
-        if all && days.is_some() {
-            exit_with("If --all is set, days cannot be passed explicitly")
-        } else if !all && days.is_none() {
-            return Days::Explicit(Vec::new());
-        }
-        match days {
-            None => Days::All,
-            Some(mut v) => {
-                v.sort_unstable();
-                v.dedup();
-                Days::Explicit(v)
-            }
-        }
-    }
-
-    fn as_vec(&self) -> Vec<Day> {
-        match self {
-            Self::All => (1..=25).map(Day::new_or_exit).collect(),
-            Self::Explicit(v) => v.clone(),
-        }
-    }
-}
+use advent2024::{
+    answers::{Answers, InputKind, Verdict},
+    bench,
+    cache::Cache,
+    description, description_path,
+    download::{
+        self, download_examples, download_input, make_client, unlock_time, Downloaded, Downloader,
+    },
+    error::Error,
+    example_path, example_paths, get_solver, input_path, leaderboard, parse_duration, read_file,
+    registry,
+    report::{self, Format, Status},
+    runner::{self, DayData},
+    scaffold, session, submit, visualize, write_file_atomic, year_dir, Answer, Day, Days, Year,
+};
 
 #[derive(Subcommand)]
 enum SubCommand {
     /// Solve AoC days
     Solve {
-        /// Directory with input data. Each file must be named e.g. "day01.txt"
-        data_dir: PathBuf,
+        /// Directory with input data. Each file must be named e.g. "2024/day01.txt"
+        #[arg(required_unless_present = "input")]
+        data_dir: Option<PathBuf>,
         /// List of days to solve (incompatible with --all)
         days: Option<Vec<Day>>,
+        /// Day to solve the files given by --input for
+        #[arg(long, requires = "input", conflicts_with_all = ["data_dir", "days", "all"])]
+        day: Option<Day>,
+        /// Solve the day given by --day for these files instead of the data directory.
+        /// Pass "-" to read from stdin
+        #[arg(
+            long,
+            num_args = 1..,
+            requires = "day",
+            conflicts_with_all = ["data_dir", "days", "all", "example"]
+        )]
+        input: Vec<PathBuf>,
         /// Solve all implemented days
         #[arg(long)]
         all: bool,
+        /// Year of Advent of Code
+        #[arg(long, default_value_t)]
+        year: Year,
+        /// Only solve this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Solve the example files, e.g. "day01.example.txt", instead of the input
+        #[arg(long)]
+        example: bool,
+        /// TOML file with known answers to check against. Defaults to "answers.toml"
+        /// in the data directory, if it exists
+        #[arg(long)]
+        answers: Option<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Number of threads to solve days on. 0 uses one thread per CPU
+        #[arg(long, short, default_value_t = 1)]
+        jobs: usize,
+        /// Report the total wall time, separately from the time of each day
+        #[arg(long)]
+        wall_time: bool,
+        /// Give up on a day if it takes longer than this, e.g. "10s", "500ms" or "2m"
+        #[arg(long, value_parser = parse_duration)]
+        timeout: Option<Duration>,
+    },
+    /// Benchmark AoC days by running each day many times
+    Bench {
+        /// Directory with input data. Each file must be named e.g. "2024/day01.txt"
+        data_dir: PathBuf,
+        /// List of days to benchmark (incompatible with --all)
+        days: Option<Vec<Day>>,
+        /// Benchmark all implemented days
+        #[arg(long)]
+        all: bool,
+        /// Year of Advent of Code
+        #[arg(long, default_value_t)]
+        year: Year,
+        /// Only benchmark this part (and parsing)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Time to run each stage before measuring, e.g. "500ms". A bare number is in seconds
+        #[arg(long, default_value = "0.5s", value_parser = parse_duration)]
+        warmup: Duration,
+        /// Approximate time to spend measuring each stage, e.g. "2s"
+        #[arg(long, default_value = "2s", value_parser = parse_duration)]
+        measure: Duration,
+        /// Minimum number of samples per stage. Each sample times as many runs as fit in 1ms
+        #[arg(long, default_value_t = 10)]
+        min_samples: usize,
+        /// Baseline file from a previous run to compare against
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Save the median timings to this file, for use as a later baseline
+        #[arg(long)]
+        save_baseline: Option<PathBuf>,
+    },
+    /// Re-solve a day whenever its input or example files change
+    Watch {
+        /// Day to watch
+        day: Day,
+        /// Directory with input data
+        #[arg(long, default_value = "data")]
+        data_dir: PathBuf,
+        /// Year of Advent of Code
+        #[arg(long, default_value_t)]
+        year: Year,
+        /// Only solve this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
+    /// Animate how a day solves its input, for days with a visualization
+    Visualize {
+        /// Day to visualize
+        day: Day,
+        /// Directory with input data
+        #[arg(long, default_value = "data")]
+        data_dir: PathBuf,
+        /// Year of Advent of Code
+        #[arg(long, default_value_t)]
+        year: Year,
+        /// Part to visualize, for days that show the parts differently
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Visualize the first example file, e.g. "day06.example.txt", instead of the input
+        #[arg(long)]
+        example: bool,
+        /// Visualize this file instead of the data directory
+        #[arg(long, conflicts_with = "example")]
+        input: Option<PathBuf>,
+        /// Time between frames, e.g. "50ms"
+        #[arg(long, default_value = "50ms", value_parser = parse_duration)]
+        delay: Duration,
+        /// Number of steps shown per frame
+        #[arg(long, default_value_t = 1)]
+        steps_per_frame: usize,
+        /// Save the animation instead of showing it, as an animated ".png" (APNG) or ".gif".
+        /// Viewers without APNG support show the last frame of a ".png"
+        #[arg(long, value_parser = image_path)]
+        output: Option<PathBuf>,
+        /// Width and height of each cell in pixels, when saving an image
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=64))]
+        scale: u32,
+    },
+    /// List implemented days and parts
+    List,
+    /// Create and register a new day module from a template
+    NewDay {
+        /// Day to create
+        day: Day,
+        /// Year of Advent of Code
+        #[arg(long, default_value_t)]
+        year: Year,
+        /// Source directory of this crate
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/src"))]
+        src_dir: PathBuf,
     },
     /// Download input data files for AoC
     Download {
-        /// Advent of code session key, 128-character hexadecimal
-        session_key: String,
-        /// Directory to download data to, creating it if necessary
+        /// Advent of code session key, 128-character hexadecimal. If not given, it is read
+        /// from the AOC_SESSION environment variable, the config file, or a prompt
+        #[arg(long)]
+        session_key: Option<String>,
+        /// Directory to download data to, creating it if necessary. Data is placed in
+        /// a subdirectory per year, e.g. "2024/day01.txt"
         data_dir: PathBuf,
         /// List of days to download (incompatible with --all)
         days: Option<Vec<Day>>,
         /// Download all released data
         #[arg(long)]
         all: bool,
+        /// Year of Advent of Code
+        #[arg(long, default_value_t)]
+        year: Year,
+        /// Also download the examples from the puzzle descriptions
+        #[arg(long)]
+        examples: bool,
+        /// Always ask the server, instead of using previously cached responses
+        #[arg(long)]
+        no_cache: bool,
+        /// If a day is not yet released, wait until it unlocks, then download it
+        #[arg(long, conflicts_with = "all")]
+        wait: bool,
+        /// Solve the days after downloading them
+        #[arg(long)]
+        solve: bool,
+    },
+    /// Show the puzzle description of a day, and save it as e.g. "2024/day01.md" in the
+    /// data directory
+    Read {
+        /// Advent of code session key, 128-character hexadecimal. If not given, it is read
+        /// from the AOC_SESSION environment variable, the config file, or a prompt
+        #[arg(long)]
+        session_key: Option<String>,
+        /// Day to read
+        day: Day,
+        /// Directory with input data
+        #[arg(long, default_value = "data")]
+        data_dir: PathBuf,
+        /// Year of Advent of Code
+        #[arg(long, default_value_t)]
+        year: Year,
+        /// Show the saved description instead of fetching it
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Show a private leaderboard
+    Leaderboard {
+        /// Advent of code session key, 128-character hexadecimal. If not given, it is read
+        /// from the AOC_SESSION environment variable, the config file, or a prompt
+        #[arg(long)]
+        session_key: Option<String>,
+        /// ID of the leaderboard, which is the number in its URL
+        id: u64,
+        /// Year of Advent of Code
+        #[arg(long, default_value_t)]
+        year: Year,
+        /// Also show when each member completed the parts of this day
+        #[arg(long)]
+        day: Option<Day>,
+    },
+    /// Submit an answer to AoC
+    Submit {
+        /// Advent of code session key, 128-character hexadecimal. If not given, it is read
+        /// from the AOC_SESSION environment variable, the config file, or a prompt
+        #[arg(long)]
+        session_key: Option<String>,
+        /// Day to submit an answer for
+        day: Day,
+        /// Year of Advent of Code
+        #[arg(long, default_value_t)]
+        year: Year,
+        /// Part to submit an answer for
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// The answer to submit
+        answer: String,
+        /// Base URL of the AoC server
+        #[arg(long, default_value = submit::BASE_URL)]
+        base_url: String,
     },
 }
 
+// Images are saved as PNG or GIF, according to their extension
+fn image_path(s: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(s);
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("png" | "gif") => Ok(path),
+        _ => Err("The file name must end in .png or .gif".to_owned()),
+    }
+}
+
 #[derive(Parser)]
 struct Cli {
     #[command(subcommand)]
     command: SubCommand,
 }
 
-fn solve(data_dir: &Path, days: Option<Vec<Day>>, all: bool) {
-    let days = Days::new(days, all);
-    let solvers: Vec<(Day, Option<Solver>)> = match days {
-        Days::All => days
-            .as_vec()
-            .iter()
-            .filter_map(|&day| get_solver(day).map(|s| (day, Some(s))))
-            .collect(),
-        Days::Explicit(v) => v.iter().map(|&day| (day, get_solver(day))).collect(),
-    };
+struct SolveOptions {
+    part: Option<u8>,
+    answers: Option<PathBuf>,
+    example: bool,
+    format: Format,
+    jobs: usize,
+    wall_time: bool,
+    timeout: Option<Duration>,
+}
+
+fn solve(
+    data_dir: &Path,
+    year: Year,
+    days: Option<Vec<Day>>,
+    all: bool,
+    options: SolveOptions,
+) -> Result<(), Error> {
+    let data = runner::load_days(data_dir, year, days, all, options.example)?;
     // If nothing to do, exit the program without an error
-    if solvers.iter().all(|(_, s)| s.is_none()) {
-        std::process::exit(0)
+    if data.iter().all(|(_, s)| s.is_none()) {
+        return Ok(());
     }
-    // Read input files
-    if !data_dir.is_dir() {
-        exit_with(&format!(
-            "Data directory \"{:?}\" is not an existing directory",
-            data_dir
-        ));
+    // Load known answers, if an answers file was given or exists in the data dir
+    let answers = options
+        .answers
+        .clone()
+        .or_else(|| Some(data_dir.join("answers.toml")).filter(|p| p.is_file()))
+        .map(|path| Answers::load(&path))
+        .transpose()?;
+    solve_data(data, answers, options)
+}
+
+// Solve the day for the given files, each labelled with its path
+fn solve_files(
+    year: Year,
+    day: Day,
+    paths: &[PathBuf],
+    options: SolveOptions,
+) -> Result<(), Error> {
+    let inputs = runner::read_inputs(paths)?;
+    // Answers files are for the user's own input, so only check if explicitly asked to
+    let answers = options.answers.as_deref().map(Answers::load).transpose()?;
+    let data = vec![(day, get_solver(year, day).map(|solver| (inputs, solver)))];
+    solve_data(data, answers, options)
+}
+
+fn solve_data(
+    data: Vec<DayData>,
+    answers: Option<Answers>,
+    options: SolveOptions,
+) -> Result<(), Error> {
+    let solve_options = runner::SolveOptions {
+        part: options.part,
+        jobs: options.jobs,
+        timeout: options.timeout,
+    };
+    let mut reports = Vec::new();
+    let summary = runner::solve_days(data, answers.as_ref(), &solve_options, |report| {
+        // Text is printed as we go, so slow days don't hold back the output of fast ones
+        if options.format == Format::Text {
+            print!("{}", report::text(&report));
+        } else {
+            reports.push(report);
+        }
+    });
+    print!("{}", report::render(options.format, &reports));
+    // This goes to stderr, such that machine-readable output on stdout stays valid
+    if options.wall_time {
+        eprintln!(
+            "Wall time: {:.2?} on {} thread(s), sum of days: {:.2?}",
+            summary.wall_time, summary.jobs, summary.summed
+        );
     }
-    // Load the data in as String, for all requested days that have a solver
-    let data: Vec<(Day, Option<(String, Solver)>)> = solvers
-        .into_iter()
-        .map(|(day, maybe_solver)| {
-            if let Some(solver) = maybe_solver {
-                let path = data_dir.join(format!("day{:02}.txt", day.0));
-                let string = std::fs::read_to_string(&path).unwrap_or_else(|_| {
-                    exit_with(&format!(
-                        "Could not read file \"{:?}\" into UTF-8 string",
-                        path
-                    ))
-                });
-                (day, Some((string, solver)))
+    summary.result()
+}
+
+// How often the watched files are checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(300);
+
+fn watch(data_dir: &Path, year: Year, day: Day, part: Option<u8>) -> Result<(), Error> {
+    let Some(solver) = get_solver(year, day) else {
+        println!("Day {}: Unimplemented!", day);
+        return Ok(());
+    };
+    // The example files are looked up again each time, so new ones are picked up
+    let watched = || {
+        let mut paths = example_paths(data_dir, day);
+        paths.insert(0, input_path(data_dir, day));
+        paths
+            .into_iter()
+            .map(|p| {
+                let modified = std::fs::metadata(&p).and_then(|m| m.modified()).ok();
+                (p, modified)
+            })
+            .collect::<Vec<_>>()
+    };
+    let parts = runner::parts(part);
+    let highlight = std::io::stdout().is_terminal();
+    let mut previous: HashMap<(PathBuf, u8), Answer> = HashMap::new();
+    let mut snapshot = watched();
+    println!(
+        "Watching day {} in {:?}. Press Ctrl-C to stop.",
+        day, data_dir
+    );
+    loop {
+        let answers = Some(data_dir.join("answers.toml"))
+            .filter(|p| p.is_file())
+            .map(|p| Answers::load(&p))
+            .transpose()?;
+        for (i, (path, modified)) in snapshot.iter().enumerate() {
+            // The input file need not exist, if there are examples
+            if modified.is_none() {
+                continue;
+            }
+            let label = path.file_name().map(|s| s.to_string_lossy().into_owned());
+            print!("{:<20}", label.as_deref().unwrap_or_default());
+            // The file may have been removed since we looked, which is not worth stopping for
+            let data = match read_file(path) {
+                Ok(data) => data,
+                Err(e) => {
+                    println!(" FAIL {}", e);
+                    continue;
+                }
+            };
+            // The input is always the first path, followed by the examples in order
+            let kind = match i {
+                0 => InputKind::Real,
+                n => InputKind::Example(n),
+            };
+            let input = runner::Input {
+                label,
+                path: Some(path.clone()),
+                data,
+                kind,
+            };
+            let report = runner::solve_input(day, input, solver, &parts, answers.as_ref(), None);
+            let solved = match &report.status {
+                Status::Solved(solved) => solved,
+                Status::Failed(failure) => {
+                    println!(" FAIL {}", failure);
+                    continue;
+                }
+                Status::Unimplemented | Status::NoExamples => {
+                    unreachable!("A single input is always solved")
+                }
+            };
+            let verdicts = || solved.parts.iter().filter_map(|p| p.verdict.as_ref());
+            // Only pass if some answer was checked, and none of them were wrong
+            let status = if verdicts().any(|v| matches!(v, Verdict::Wrong(_))) {
+                "FAIL"
+            } else if verdicts().any(|v| *v == Verdict::Correct) {
+                "pass"
             } else {
-                (day, None)
+                "?"
+            };
+            print!(" {:<4} [{:.2?}]", status, solved.time);
+            for part in &solved.parts {
+                let mut answer = part.answer.to_string();
+                let key = (path.clone(), part.part);
+                if let Some(old) = previous.get(&key).filter(|&old| old != &part.answer) {
+                    answer = format!("{} (was {})", answer, old);
+                    if highlight {
+                        // Bold yellow
+                        answer = format!("\x1b[1;33m{}\x1b[0m", answer);
+                    }
+                }
+                print!("  Part {}: {}", part.part, answer);
+                if let Some(Verdict::Wrong(expected)) = &part.verdict {
+                    print!(" (expected {})", expected);
+                }
+                previous.insert(key, part.answer.clone());
             }
-        })
-        .collect();
-    // Execute and time each
-    for (day, maybe_data_solver) in data {
-        if let Some((data, solver)) = maybe_data_solver {
-            let begin = Instant::now();
-            let (a, b) = solver(&data);
-            let duration = Instant::duration_since(&Instant::now(), begin);
-            println!(
-                "Day {:02} [{:.2?}]:\n  Part 1: {}\n  Part 2: {}\n",
-                day.0, duration, a, b
-            );
-        } else {
-            println!("Day {:02}: Unimplemented!\n", day.0);
+            println!();
+        }
+        // Wait for a change
+        while watched() == snapshot {
+            std::thread::sleep(WATCH_INTERVAL);
         }
+        // Editors may write a file in several steps, so wait until the files have
+        // been unchanged for a moment
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            let new = watched();
+            if new == snapshot {
+                break;
+            }
+            snapshot = new;
+        }
+        println!();
     }
 }
 
-enum Downloaded {
-    NotYetReleased,
-    Data(String),
+fn bench(
+    data_dir: &Path,
+    year: Year,
+    days: Option<Vec<Day>>,
+    all: bool,
+    options: bench::Options,
+) -> Result<(), Error> {
+    let data = runner::load_days(data_dir, year, days, all, false)?;
+    bench::bench_days(year, data, &options, |day| {
+        let Some(stages) = &day.stages else {
+            println!("Day {}: Unimplemented!", day.day);
+            return;
+        };
+        println!("Day {}:", day.day);
+        for part in &day.unimplemented {
+            println!("  Part {}: Unimplemented!", part);
+        }
+        for stage in stages {
+            print!("  {}: {}", stage.stage, stage.stats);
+            if let Some(change) = stage.change {
+                let verdict = if change > 0.0 { "slower" } else { "faster" };
+                print!(" [{:+.2}%, {}]", change, verdict);
+            }
+            println!();
+        }
+    })?;
+    Ok(())
+}
+
+struct DownloadOptions {
+    examples: bool,
+    no_cache: bool,
+    wait: bool,
+    solve: bool,
 }
 
-fn download(data_dir: &Path, session_key: &str, days: Option<Vec<Day>>, all: bool) {
-    let days = Days::new(days, all);
+fn download(
+    data_dir: &Path,
+    year: Year,
+    session_key: Option<&str>,
+    days: Option<Vec<Day>>,
+    all: bool,
+    options: DownloadOptions,
+) -> Result<(), Error> {
+    let to_solve = options.solve.then(|| days.clone());
+    let days = Days::new(days, all)?;
     // If we need to download no days, exit early
     if let Days::Explicit(ref v) = days {
         if v.is_empty() {
-            std::process::exit(0)
+            return Ok(());
         }
     }
     let mut should_create_dir = false;
@@ -215,22 +514,28 @@ fn download(data_dir: &Path, session_key: &str, days: Option<Vec<Day>>, all: boo
     if !data_dir.is_dir() {
         should_create_dir = true;
         if data_dir.exists() {
-            exit_with(&format!(
-                "A file at the given data directory exists, but is a not a directory: \"{:?}\"",
-                data_dir
-            ))
+            return Err(Error::NotADirectory(data_dir.to_owned()));
         }
     }
-    // This allows us to lazily construct the client, only if we need it
-    let client = OnceCell::new();
-    for day in days.as_vec() {
-        let path = data_dir.join(format!("day{:02}.txt", day.0));
+    // This allows us to lazily construct the downloader, only if we need it
+    let mut downloader = None;
+    for day in days.as_vec(year) {
+        let path = input_path(data_dir, day);
         // If the path exist, no need to download anything
         if path.is_file() {
-            println!("Day {:02} is already downloaded", day.0);
-            continue;
+            println!("Day {} is already downloaded", day);
         } else {
-            let data = download_input(client.get_or_init(|| make_client(session_key)), day);
+            if options.wait {
+                // Set up the downloader first, so that we don't prompt for the session key
+                // after having waited
+                lazy_downloader(&mut downloader, session_key, options.no_cache)?;
+                wait_until(unlock_time(year, day), day);
+            }
+            let data = download_input(
+                lazy_downloader(&mut downloader, session_key, options.no_cache)?,
+                year,
+                day,
+            )?;
             match data {
                 Downloaded::NotYetReleased => match days {
                     // Since we know that the days returned from days.as_vec() are in sorted order,
@@ -239,79 +544,377 @@ fn download(data_dir: &Path, session_key: &str, days: Option<Vec<Day>>, all: boo
                     Days::All => break,
                     // If the user explicitly requested an unreleased day, we can't fulfill the
                     // request and need to exit
-                    Days::Explicit(_) => exit_with(&format!(
-                        "Explicitly requested day {:02}, but this day is not yet released.",
-                        day.0
-                    )),
+                    Days::Explicit(_) => return Err(Error::NotYetReleased(day)),
                 },
                 // If downloaded a file, save it
                 Downloaded::Data(s) => {
                     if should_create_dir {
-                        std::fs::create_dir_all(data_dir).unwrap_or_else(|_| {
-                            exit_with(&format!(
-                                "Could not create data directory, and is not an existing directory: \"{:?}\"",
-                                data_dir
-                            ))
-                        });
+                        std::fs::create_dir_all(data_dir).map_err(|e| {
+                            Error::Io(
+                                format!("Could not create data directory \"{:?}\"", data_dir),
+                                e,
+                            )
+                        })?;
                         should_create_dir = false;
                     }
-                    let mut file = File::create_new(&path).unwrap_or_else(|_| {
-                        exit_with(&format!("Could not create new file at path \"{:?}\"", path))
-                    });
-                    file.write_all(s.as_bytes()).unwrap_or_else(|_| {
-                        exit_with(&format!(
-                            "Error when writing data to file at \"{:?}\"",
-                            path
-                        ))
-                    });
-                    println!("Downloaded day {:02}", day.0)
+                    write_file_atomic(&path, &s)?;
+                    println!("Downloaded day {}", day)
                 }
             }
         }
+        // We only get here if the day is released, so the puzzle page exists
+        if options.examples {
+            if example_path(data_dir, day, 1).is_file() {
+                println!("Examples for day {} are already downloaded", day);
+            } else {
+                let blocks = download_examples(
+                    lazy_downloader(&mut downloader, session_key, options.no_cache)?,
+                    year,
+                    day,
+                )?;
+                for (i, block) in blocks.iter().enumerate() {
+                    write_file_atomic(&example_path(data_dir, day, i + 1), block)?;
+                }
+                println!("Downloaded {} examples for day {}", blocks.len(), day)
+            }
+        }
     }
+    if let Some(days) = to_solve {
+        println!();
+        let options = SolveOptions {
+            part: None,
+            answers: None,
+            example: false,
+            format: Format::Text,
+            jobs: 1,
+            wall_time: false,
+            timeout: None,
+        };
+        solve(data_dir, year, days, all, options)?;
+    }
+    Ok(())
 }
 
-fn make_client(session: &str) -> Client {
-    // Verify it's formatted correctly
-    let bytes = session.as_bytes();
-    if bytes.len() != 128 || !bytes.iter().all(|&b| b.is_ascii_hexdigit()) {
-        exit_with("Session key not a 128-character hexadecimal string")
+fn read(
+    data_dir: &Path,
+    year: Year,
+    day: Day,
+    session_key: Option<&str>,
+    offline: bool,
+) -> Result<(), Error> {
+    let path = description_path(data_dir, day);
+    let markdown = if offline {
+        read_file(&path)?
+    } else {
+        let markdown = description::fetch(&mut make_downloader(session_key, false)?, year, day)?;
+        std::fs::create_dir_all(data_dir).map_err(|e| {
+            Error::Io(
+                format!("Could not create data directory \"{:?}\"", data_dir),
+                e,
+            )
+        })?;
+        write_file_atomic(&path, &markdown)?;
+        markdown
     };
-    let mut headers = reqwest::header::HeaderMap::default();
-    let cookie =
-        reqwest::header::HeaderValue::from_str(format!("session={}", session).as_str()).unwrap();
-    headers.insert("Cookie", cookie);
-    Client::builder().default_headers(headers).build().unwrap()
+    print!("{}", markdown);
+    Ok(())
 }
 
-fn download_input(client: &Client, day: Day) -> Downloaded {
-    let url = format!("https://adventofcode.com/2024/day/{}/input", day.0);
-    let resp = client.get(url.as_str()).send().unwrap();
-    if !resp.status().is_success() {
-        let text = resp.text().unwrap();
-        // The response will begin with this string if the day is not yet released
-        if text.contains("Please don't repeatedly request this endpoint before it unlocks") {
-            return Downloaded::NotYetReleased;
+// Sleep until the time, showing a countdown
+fn wait_until(time: SystemTime, day: Day) {
+    let mut waited = false;
+    while let Ok(left) = time.duration_since(SystemTime::now()) {
+        let secs = left.as_secs();
+        eprint!(
+            "\rDay {} unlocks in {:02}:{:02}:{:02}",
+            day,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        );
+        let _ = std::io::stderr().flush();
+        // Sleep to the next whole second left, such that the countdown ticks evenly
+        let tick = Duration::new(0, left.subsec_nanos());
+        std::thread::sleep(if tick.is_zero() {
+            Duration::from_secs(1)
         } else {
-            exit_with(&format!("Error when processing request:\n{}", text))
+            tick
+        });
+        waited = true;
+    }
+    if waited {
+        eprintln!();
+        // Give the server a moment, in case its clock is slightly behind ours
+        std::thread::sleep(Duration::from_secs(1));
+    }
+}
+
+struct VisualizeOptions {
+    part: u8,
+    delay: Duration,
+    steps_per_frame: usize,
+    output: Option<PathBuf>,
+    scale: u32,
+}
+
+fn visualize(
+    year: Year,
+    day: Day,
+    input_path: &Path,
+    options: VisualizeOptions,
+) -> Result<(), Error> {
+    let visualizer =
+        visualize::get_visualizer(year, day).ok_or(Error::NoVisualization(year, day))?;
+    let animation = visualizer(&read_file(input_path)?, options.part);
+    let steps = options.steps_per_frame;
+    match options.output {
+        Some(path) if path.extension().is_some_and(|e| e == "png") => {
+            visualize::write_png(&path, &animation, options.scale, steps, options.delay)
+        }
+        Some(path) => visualize::write_gif(&path, &animation, options.scale, steps, options.delay),
+        // Animating only makes sense in a terminal, so otherwise just print the last frame
+        None if std::io::stdout().is_terminal() => visualize::play(
+            &animation,
+            &mut std::io::stdout().lock(),
+            steps,
+            options.delay,
+        )
+        .map_err(|e| Error::Io("Could not write to the terminal".to_owned(), e)),
+        None => {
+            print!("{}", animation.last_frame());
+            Ok(())
         }
     }
-    Downloaded::Data(resp.text().unwrap())
 }
 
-fn main() {
-    let cli = Cli::parse();
+fn lazy_downloader<'a>(
+    downloader: &'a mut Option<Downloader>,
+    session_key: Option<&str>,
+    no_cache: bool,
+) -> Result<&'a mut Downloader, Error> {
+    if downloader.is_none() {
+        *downloader = Some(make_downloader(session_key, no_cache)?);
+    }
+    Ok(downloader
+        .as_mut()
+        .expect("The downloader was just created"))
+}
+
+fn make_downloader(session_key: Option<&str>, no_cache: bool) -> Result<Downloader, Error> {
+    let session = session::resolve(session_key)?;
+    let client = make_client(&session, &download::user_agent()?)?;
+    let options = download::Options {
+        cache: Cache::for_session(&session).filter(|_| !no_cache),
+        ..Default::default()
+    };
+    Ok(Downloader::new(client, options))
+}
+
+fn run(cli: Cli) -> Result<(), Error> {
     match cli.command {
         SubCommand::Solve {
             data_dir,
             days,
+            day,
+            input,
             all,
-        } => solve(&data_dir, days, all),
+            year,
+            part,
+            example,
+            answers,
+            format,
+            jobs,
+            wall_time,
+            timeout,
+        } => {
+            let options = SolveOptions {
+                part,
+                answers,
+                example,
+                format,
+                jobs,
+                wall_time,
+                timeout,
+            };
+            match (data_dir, day) {
+                (Some(data_dir), _) => solve(&year_dir(&data_dir, year), year, days, all, options),
+                (None, Some(day)) => solve_files(year, day, &input, options),
+                // clap requires either a data dir, or --input which requires --day
+                (None, None) => unreachable!(),
+            }
+        }
+        SubCommand::Bench {
+            data_dir,
+            days,
+            all,
+            year,
+            part,
+            warmup,
+            measure,
+            min_samples,
+            baseline,
+            save_baseline,
+        } => {
+            let options = bench::Options {
+                part,
+                warmup,
+                measure,
+                min_samples,
+                baseline,
+                save_baseline,
+            };
+            bench(&year_dir(&data_dir, year), year, days, all, options)
+        }
+        SubCommand::Watch {
+            day,
+            data_dir,
+            year,
+            part,
+        } => watch(&year_dir(&data_dir, year), year, day, part),
+        SubCommand::Visualize {
+            day,
+            data_dir,
+            year,
+            part,
+            example,
+            input,
+            delay,
+            steps_per_frame,
+            output,
+            scale,
+        } => {
+            let data_dir = year_dir(&data_dir, year);
+            let input = input.unwrap_or_else(|| {
+                if example {
+                    example_path(&data_dir, day, 1)
+                } else {
+                    input_path(&data_dir, day)
+                }
+            });
+            let options = VisualizeOptions {
+                part,
+                delay,
+                steps_per_frame,
+                output,
+                scale,
+            };
+            visualize(year, day, &input, options)
+        }
+        SubCommand::List => {
+            for registration in registry() {
+                let parts: Vec<_> = (1..=2)
+                    .filter(|&p| registration.parts[p - 1])
+                    .map(|p| format!("Part {}", p))
+                    .collect();
+                if parts.is_empty() {
+                    println!(
+                        "{} Day {}: No parts implemented",
+                        registration.year, registration.day
+                    );
+                } else {
+                    println!(
+                        "{} Day {}: {}",
+                        registration.year,
+                        registration.day,
+                        parts.join(", ")
+                    );
+                }
+            }
+            Ok(())
+        }
+        SubCommand::NewDay { day, year, src_dir } => {
+            let path = scaffold::new_day(&src_dir, year, day)?;
+            println!("Created {:?}", path);
+            Ok(())
+        }
         SubCommand::Download {
             session_key,
             data_dir,
             days,
             all,
-        } => download(&data_dir, session_key.trim(), days, all),
+            year,
+            examples,
+            no_cache,
+            wait,
+            solve,
+        } => {
+            let options = DownloadOptions {
+                examples,
+                no_cache,
+                wait,
+                solve,
+            };
+            download(
+                &year_dir(&data_dir, year),
+                year,
+                session_key.as_deref(),
+                days,
+                all,
+                options,
+            )
+        }
+        SubCommand::Read {
+            session_key,
+            day,
+            data_dir,
+            year,
+            offline,
+        } => read(
+            &year_dir(&data_dir, year),
+            year,
+            day,
+            session_key.as_deref(),
+            offline,
+        ),
+        SubCommand::Leaderboard {
+            session_key,
+            id,
+            year,
+            day,
+        } => {
+            // The cache is what keeps us from fetching the leaderboard too often
+            let mut downloader = make_downloader(session_key.as_deref(), false)?;
+            let leaderboard = leaderboard::fetch(&mut downloader, year, id)?;
+            print!("{}", leaderboard::render(&leaderboard, year));
+            if let Some(day) = day {
+                println!();
+                print!("{}", leaderboard::render_day(&leaderboard, year, day));
+            }
+            Ok(())
+        }
+        SubCommand::Submit {
+            session_key,
+            day,
+            year,
+            part,
+            answer,
+            base_url,
+        } => {
+            let session = session::resolve(session_key.as_deref())?;
+            let client = make_client(&session, &download::user_agent()?)?;
+            let outcome = submit::submit(&client, &base_url, year, day, part, answer.trim())?;
+            if outcome == submit::Outcome::Correct {
+                // The puzzle page changes when a part is solved, so the cached one is stale
+                if let Some(cache) = Cache::for_session(&session) {
+                    cache.remove(&format!("{}/{}/day/{}", base_url, year, day.get()));
+                }
+            }
+            match outcome {
+                submit::Outcome::Correct | submit::Outcome::AlreadySolved => {
+                    println!("{}", outcome);
+                    Ok(())
+                }
+                _ => Err(Error::AnswerRejected(outcome)),
+            }
+        }
+    }
+}
+
+// This is the only place the program exits with an error.
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli) {
+        eprintln!("{}", e);
+        std::process::exit(e.exit_code())
     }
 }
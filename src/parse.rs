@@ -0,0 +1,242 @@
+use std::{fmt::Display, marker::PhantomData};
+
+/// Integer types that can be parsed by `integers`
+pub trait Integer: Copy {
+    const SIGNED: bool;
+    const ZERO: Self;
+
+    /// `self * 10 + digit`, or `self * 10 - digit` if `negative`, unless it overflows
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal; $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = $signed;
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    // Negative numbers are built downwards, so that e.g. i8::MIN can be parsed
+                    let shifted = self.checked_mul(10)?;
+                    if negative {
+                        shifted.checked_sub(digit as $t)
+                    } else {
+                        shifted.checked_add(digit as $t)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(false; u8, u16, u32, u64, u128, usize);
+impl_integer!(true; i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The number does not fit in the type. Contains the name of the type.
+    Overflow(&'static str),
+    /// A number was expected, but there are no more numbers
+    MissingNumber,
+}
+
+/// An error when parsing, at a line and column counted from 1. The column is in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}, column {}: ", self.line, self.column)?;
+        match self.kind {
+            ErrorKind::Overflow(t) => write!(f, "Number does not fit in {}", t),
+            ErrorKind::MissingNumber => write!(f, "Expected a number"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Iterator over the integers in text, created by `integers`
+pub struct Integers<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+    // Index of the first byte of the current line
+    line_start: usize,
+    integer: PhantomData<T>,
+}
+
+/// The integers in the text, in order. Everything else is skipped as separators, so e.g.
+/// "12: 3,4" has the integers 12, 3 and 4. For signed types, a `-` right before a digit
+/// makes the number negative. For unsigned types, it is a separator like any other.
+/// The iterator does not allocate, and numbers that don't fit in `T` are errors.
+pub fn integers<T: Integer>(s: &(impl AsRef<[u8]> + ?Sized)) -> Integers<'_, T> {
+    Integers {
+        bytes: s.as_ref(),
+        pos: 0,
+        line: 1,
+        line_start: 0,
+        integer: PhantomData,
+    }
+}
+
+impl<T: Integer> Integers<'_, T> {
+    /// Count lines from `line` instead of 1, for when the text is a line of some input
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// The next integer, which must be there
+    pub fn expect_next(&mut self) -> Result<T, ParseError> {
+        self.next()
+            .unwrap_or_else(|| Err(self.error(self.pos, ErrorKind::MissingNumber)))
+    }
+
+    fn error(&self, pos: usize, kind: ErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: pos - self.line_start + 1,
+            kind,
+        }
+    }
+}
+
+impl<T: Integer> Iterator for Integers<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip to the start of the next number
+        loop {
+            let b = *self.bytes.get(self.pos)?;
+            if b.is_ascii_digit()
+                || (T::SIGNED
+                    && b == b'-'
+                    && self.bytes.get(self.pos + 1).is_some_and(u8::is_ascii_digit))
+            {
+                break;
+            }
+            self.pos += 1;
+            if b == b'\n' {
+                self.line += 1;
+                self.line_start = self.pos;
+            }
+        }
+        let start = self.pos;
+        let negative = self.bytes[start] == b'-';
+        self.pos += negative as usize;
+        // On overflow, the rest of the digits are still consumed, so that the iterator
+        // can continue with the next number
+        let mut n = Some(T::ZERO);
+        while let Some(&b) = self.bytes.get(self.pos).filter(|b| b.is_ascii_digit()) {
+            n = n.and_then(|n| n.push_digit(b - b'0', negative));
+            self.pos += 1;
+        }
+        Some(n.ok_or_else(|| self.error(start, ErrorKind::Overflow(std::any::type_name::<T>()))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{integers, ErrorKind, Integer, ParseError};
+    use std::{fmt::Debug, str::FromStr};
+
+    #[test]
+    fn test_integers() {
+        let numbers: Result<Vec<i32>, _> = integers("12: 3,-4\n5-6 --7").collect();
+        assert_eq!(numbers, Ok(vec![12, 3, -4, 5, -6, -7]));
+        let numbers: Result<Vec<u32>, _> = integers("12: 3,-4\n5-6 --7").collect();
+        assert_eq!(numbers, Ok(vec![12, 3, 4, 5, 6, 7]));
+        assert_eq!(
+            integers::<i8>("-128 127").collect::<Result<Vec<_>, _>>(),
+            Ok(vec![-128, 127])
+        );
+
+        let mut numbers = integers::<u8>("1 2\n 300 4");
+        assert_eq!(numbers.next(), Some(Ok(1)));
+        assert_eq!(numbers.next(), Some(Ok(2)));
+        let error = numbers.next().unwrap().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 2, column 2: Number does not fit in u8"
+        );
+        assert_eq!(numbers.next(), Some(Ok(4)));
+        assert_eq!(
+            numbers.expect_next(),
+            Err(ParseError {
+                line: 2,
+                column: 7,
+                kind: ErrorKind::MissingNumber
+            })
+        );
+        assert_eq!(
+            integers::<u8>("x 256").on_line(10).next(),
+            Some(Err(ParseError {
+                line: 10,
+                column: 3,
+                kind: ErrorKind::Overflow("u8")
+            }))
+        );
+    }
+
+    // xorshift64, so the test is deterministic without a dependency on a random crate
+    fn random(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    // Compare against finding the numbers with a regex, and parsing them with `str::parse`
+    fn fuzz<T>(seed: u64)
+    where
+        T: Integer + FromStr + PartialEq + Debug,
+    {
+        let number = regex::Regex::new(if T::SIGNED { r"-?\d+" } else { r"\d+" }).unwrap();
+        let alphabet = b"0123456789-- \n,:x";
+        let mut state = seed;
+        for _ in 0..2000 {
+            let len = random(&mut state) % 40;
+            let s: String = (0..len)
+                .map(|_| alphabet[(random(&mut state) % alphabet.len() as u64) as usize] as char)
+                .collect();
+            let expected: Vec<(usize, Option<T>)> = number
+                .find_iter(&s)
+                .map(|m| (m.start(), m.as_str().parse().ok()))
+                .collect();
+            let actual: Vec<(usize, Option<T>)> = integers::<T>(&s)
+                .map(|r| match r {
+                    Ok(n) => (usize::MAX, Some(n)),
+                    Err(e) => {
+                        // Convert the line and column back to an index to compare positions
+                        let line_start: usize =
+                            s.split('\n').take(e.line - 1).map(|l| l.len() + 1).sum();
+                        (line_start + e.column - 1, None)
+                    }
+                })
+                .collect();
+            // Only errors have positions, so ignore the positions of successful numbers
+            let expected: Vec<_> = expected
+                .into_iter()
+                .map(|(start, n)| (if n.is_some() { usize::MAX } else { start }, n))
+                .collect();
+            assert_eq!(actual, expected, "Input {:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_fuzz() {
+        fuzz::<u8>(1);
+        fuzz::<i8>(2);
+        fuzz::<u16>(3);
+        fuzz::<i32>(4);
+        fuzz::<u64>(5);
+        fuzz::<i128>(6);
+    }
+}
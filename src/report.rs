@@ -0,0 +1,358 @@
+use std::{fmt::Write, path::PathBuf, time::Duration};
+
+use serde_json::{json, Value};
+
+use crate::{answers::Verdict, runner::Failure, Answer, Day};
+
+/// How the results of solving days are printed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Human readable, printed as each day is solved
+    Text,
+    /// An array with an object per day and input
+    Json,
+    /// A row per day, input and part
+    Csv,
+    /// A table with a row per day and input
+    Markdown,
+}
+
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Answer,
+    pub time: Duration,
+    /// Only present if there is an answers file to check against
+    pub verdict: Option<Verdict>,
+}
+
+/// The result of solving one input of a day
+pub struct DayReport {
+    pub day: Day,
+    // Shown in the output to tell inputs apart, e.g. the file name of an example
+    pub label: Option<String>,
+    pub path: Option<PathBuf>,
+    pub status: Status,
+}
+
+pub enum Status {
+    /// The day has no solver
+    Unimplemented,
+    /// Examples were to be solved, but the day has none
+    NoExamples,
+    Solved(Solved),
+    Failed(Failure),
+}
+
+impl Status {
+    // Name of the status in machine-readable output
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Unimplemented => "unimplemented",
+            Self::NoExamples => "no_examples",
+            Self::Solved(_) => "solved",
+            Self::Failed(Failure::Panicked(_)) => "panicked",
+            Self::Failed(Failure::TimedOut(_)) => "timed_out",
+        }
+    }
+
+    pub fn solved(&self) -> Option<&Solved> {
+        match self {
+            Self::Solved(solved) => Some(solved),
+            _ => None,
+        }
+    }
+}
+
+pub struct Solved {
+    pub time: Duration,
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+pub fn render(format: Format, reports: &[DayReport]) -> String {
+    match format {
+        Format::Text => reports.iter().map(text).collect(),
+        Format::Json => json(reports),
+        Format::Csv => csv(reports),
+        Format::Markdown => markdown(reports),
+    }
+}
+
+pub fn text(report: &DayReport) -> String {
+    let solved = match &report.status {
+        Status::Unimplemented => return format!("Day {}: Unimplemented!\n\n", report.day),
+        Status::NoExamples => return format!("Day {}: No examples\n\n", report.day),
+        Status::Failed(failure) => {
+            return match &report.label {
+                None => format!("Day {}: {}\n\n", report.day, failure),
+                Some(label) => format!("Day {} ({}): {}\n\n", report.day, label, failure),
+            }
+        }
+        Status::Solved(solved) => solved,
+    };
+    let mut s = match &report.label {
+        None => format!("Day {} [{:.2?}]:\n", report.day, solved.time),
+        Some(label) => format!("Day {} ({}) [{:.2?}]:\n", report.day, label, solved.time),
+    };
+    writeln!(s, "  Parse [{:.2?}]", solved.parse_time).unwrap();
+    for part in &solved.parts {
+        write!(
+            s,
+            "  Part {} [{:.2?}]: {}",
+            part.part, part.time, part.answer
+        )
+        .unwrap();
+        if let Some(verdict) = &part.verdict {
+            write!(s, " ({})", verdict).unwrap();
+        }
+        s.push('\n');
+    }
+    s.push('\n');
+    s
+}
+
+fn json(reports: &[DayReport]) -> String {
+    let reports: Vec<Value> = reports
+        .iter()
+        .map(|report| {
+            let solved = report.status.solved();
+            let parts: Vec<Value> = solved
+                .map(|s| s.parts.as_slice())
+                .unwrap_or_default()
+                .iter()
+                .map(|part| {
+                    json!({
+                        "part": part.part,
+                        "implemented": part.answer != Answer::Unimplemented,
+                        "answer": solved_answer(&part.answer),
+                        "time_ns": part.time.as_nanos() as u64,
+                        "verdict": part.verdict.as_ref().map(Verdict::name),
+                    })
+                })
+                .collect();
+            json!({
+                "day": report.day.get(),
+                "label": report.label,
+                "input": report.path.as_ref().map(|p| p.to_string_lossy()),
+                "status": report.status.name(),
+                "error": error(&report.status),
+                "time_ns": solved.map(|s| s.time.as_nanos() as u64),
+                "parse_ns": solved.map(|s| s.parse_time.as_nanos() as u64),
+                "parts": parts,
+            })
+        })
+        .collect();
+    let mut s = serde_json::to_string_pretty(&reports).expect("JSON values always serialize");
+    s.push('\n');
+    s
+}
+
+fn csv(reports: &[DayReport]) -> String {
+    let mut s = "day,label,input,status,error,part,implemented,answer,parse_ns,time_ns,verdict\n"
+        .to_owned();
+    for report in reports {
+        let label = csv_field(report.label.as_deref().unwrap_or_default());
+        let path = report
+            .path
+            .as_ref()
+            .map(|p| csv_field(&p.to_string_lossy()))
+            .unwrap_or_default();
+        let Some(solved) = report.status.solved() else {
+            // Days that were not solved have no parts to list, so they get a single row
+            writeln!(
+                s,
+                "{},{},{},{},{},,,,,,",
+                report.day.get(),
+                label,
+                path,
+                report.status.name(),
+                csv_field(&error(&report.status).unwrap_or_default())
+            )
+            .unwrap();
+            continue;
+        };
+        for part in &solved.parts {
+            writeln!(
+                s,
+                "{},{},{},{},,{},{},{},{},{},{}",
+                report.day.get(),
+                label,
+                path,
+                report.status.name(),
+                part.part,
+                part.answer != Answer::Unimplemented,
+                csv_field(solved_answer(&part.answer).unwrap_or_default()),
+                solved.parse_time.as_nanos(),
+                part.time.as_nanos(),
+                part.verdict.as_ref().map(Verdict::name).unwrap_or_default(),
+            )
+            .unwrap();
+        }
+    }
+    s
+}
+
+fn markdown(reports: &[DayReport]) -> String {
+    // All days are solved for the same parts, so any implemented day has the columns
+    let parts: Vec<u8> = reports
+        .iter()
+        .find_map(|r| r.status.solved())
+        .map(|s| s.parts.iter().map(|p| p.part).collect())
+        .unwrap_or_default();
+    let mut s = "| Day | Input |".to_owned();
+    for part in &parts {
+        write!(s, " Part {} |", part).unwrap();
+    }
+    s.push_str(" Parse | Total |\n|----:|-------|");
+    s.push_str(&"-------:|".repeat(parts.len()));
+    s.push_str("------:|------:|\n");
+    for report in reports {
+        let input = report
+            .label
+            .clone()
+            .or_else(|| {
+                let name = report.path.as_ref()?.file_name()?;
+                Some(name.to_string_lossy().into_owned())
+            })
+            .unwrap_or_default();
+        write!(s, "| {} | {} |", report.day, markdown_cell(&input)).unwrap();
+        match &report.status {
+            Status::Unimplemented => {
+                s.push_str(&" Unimplemented |".repeat(parts.len()));
+                s.push_str(" | |\n");
+            }
+            Status::NoExamples => {
+                s.push_str(&" No examples |".repeat(parts.len()));
+                s.push_str(" | |\n");
+            }
+            Status::Failed(failure) => {
+                let cell = format!(" {} |", markdown_cell(&failure.to_string()));
+                s.push_str(&cell.repeat(parts.len()));
+                s.push_str(" | |\n");
+            }
+            Status::Solved(solved) => {
+                for part in &solved.parts {
+                    match &part.answer {
+                        Answer::Solved(answer) => {
+                            write!(s, " {} ({:.2?}) |", markdown_cell(answer), part.time)
+                        }
+                        Answer::Unimplemented => write!(s, " Unimplemented |"),
+                    }
+                    .unwrap();
+                }
+                writeln!(s, " {:.2?} | {:.2?} |", solved.parse_time, solved.time).unwrap();
+            }
+        }
+    }
+    s
+}
+
+fn error(status: &Status) -> Option<String> {
+    match status {
+        Status::Failed(failure) => Some(failure.to_string()),
+        _ => None,
+    }
+}
+
+fn solved_answer(answer: &Answer) -> Option<&str> {
+    match answer {
+        Answer::Solved(s) => Some(s),
+        Answer::Unimplemented => None,
+    }
+}
+
+// Fields with separators, quotes or newlines are quoted, with quotes doubled
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, DayReport, Format, PartReport, Solved, Status};
+    use crate::{answers::Verdict, runner::Failure, Answer, Day};
+    use serde_json::{json, Value};
+    use std::{path::PathBuf, time::Duration};
+
+    fn reports() -> Vec<DayReport> {
+        let part = |part, answer: Answer, micros, verdict| PartReport {
+            part,
+            answer,
+            time: Duration::from_micros(micros),
+            verdict,
+        };
+        vec![
+            DayReport {
+                day: Day::new(1).unwrap(),
+                label: None,
+                path: Some(PathBuf::from("data/2024/day01.txt")),
+                status: Status::Solved(Solved {
+                    time: Duration::from_micros(100),
+                    parse_time: Duration::from_micros(70),
+                    parts: vec![
+                        part(
+                            1,
+                            Answer::Solved("421".to_owned()),
+                            4,
+                            Some(Verdict::Correct),
+                        ),
+                        part(2, Answer::Unimplemented, 0, Some(Verdict::Unknown)),
+                    ],
+                }),
+            },
+            DayReport {
+                day: Day::new(2).unwrap(),
+                label: None,
+                path: None,
+                status: Status::Unimplemented,
+            },
+            DayReport {
+                day: Day::new(6).unwrap(),
+                label: None,
+                path: Some(PathBuf::from("data/2024/day06.txt")),
+                status: Status::Failed(Failure::Panicked("explicit panic".to_owned())),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render() {
+        let json: Value = serde_json::from_str(&render(Format::Json, &reports())).unwrap();
+        assert_eq!(
+            json,
+            json!([
+                {"day": 1, "label": null, "input": "data/2024/day01.txt", "status": "solved", "error": null, "time_ns": 100000, "parse_ns": 70000, "parts": [
+                    {"part": 1, "implemented": true, "answer": "421", "time_ns": 4000, "verdict": "correct"},
+                    {"part": 2, "implemented": false, "answer": null, "time_ns": 0, "verdict": "unknown"},
+                ]},
+                {"day": 2, "label": null, "input": null, "status": "unimplemented", "error": null, "time_ns": null, "parse_ns": null, "parts": []},
+                {"day": 6, "label": null, "input": "data/2024/day06.txt", "status": "panicked", "error": "panicked: explicit panic", "time_ns": null, "parse_ns": null, "parts": []},
+            ])
+        );
+        assert_eq!(
+            render(Format::Csv, &reports()),
+            "day,label,input,status,error,part,implemented,answer,parse_ns,time_ns,verdict
+1,,data/2024/day01.txt,solved,,1,true,421,70000,4000,correct
+1,,data/2024/day01.txt,solved,,2,false,,70000,0,unknown
+2,,,unimplemented,,,,,,,
+6,,data/2024/day06.txt,panicked,panicked: explicit panic,,,,,,
+"
+        );
+        assert_eq!(
+            render(Format::Markdown, &reports()),
+            "| Day | Input | Part 1 | Part 2 | Parse | Total |
+|----:|-------|-------:|-------:|------:|------:|
+| 01 | day01.txt | 421 (4.00µs) | Unimplemented | 70.00µs | 100.00µs |
+| 02 |  | Unimplemented | Unimplemented | | |
+| 06 | day06.txt | panicked: explicit panic | panicked: explicit panic | | |
+"
+        );
+    }
+}
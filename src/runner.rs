@@ -0,0 +1,534 @@
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{mpsc, Mutex, Once},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::{Answers, InputKind, Verdict},
+    error::Error,
+    example_paths, get_solver, input_path, load_input, read_file, registry,
+    report::{DayReport, PartReport, Solved, Status},
+    Day, Days, Solver, Year,
+};
+
+// Threads running isolated functions get this name, so their panics can be recognized
+const ISOLATED_THREAD: &str = "isolated";
+
+/// Number of threads to use if the user asks for one per CPU
+pub fn available_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Apply `f` to each item on `jobs` threads, and pass the results to `output` in the
+/// order of the items. Each result is output as soon as all results before it are.
+pub fn run_ordered<T, R, F, O>(items: Vec<T>, jobs: usize, f: F, mut output: O)
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+    O: FnMut(R),
+{
+    if jobs <= 1 {
+        items.into_iter().for_each(|item| output(f(item)));
+        return;
+    }
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        let (f, queue) = (&f, &queue);
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            scope.spawn(move || {
+                while let Some((i, item)) = queue.lock().unwrap().next() {
+                    // If the receiver is gone, nobody wants the remaining results
+                    if sender.send((i, f(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // Such that the loop below ends when all workers are done
+        drop(sender);
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next) {
+                output(result);
+                next += 1;
+            }
+        }
+    });
+}
+
+/// Why an isolated function did not return
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Panicked(msg) => write!(f, "panicked: {}", msg),
+            Self::TimedOut(d) => write!(f, "timed out after {:?}", d),
+        }
+    }
+}
+
+/// Run `f` on its own thread, such that a panic in it is returned as an error instead
+/// of taking down the caller. If it has not returned after `timeout`, it is abandoned:
+/// It keeps running in the background, but its result is never used.
+pub fn isolate<R, F>(f: F, timeout: Option<Duration>) -> Result<R, Failure>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    // The panic is reported by the caller, so the default hook should not also print it
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() != Some(ISOLATED_THREAD) {
+                default_hook(info)
+            }
+        }));
+    });
+    let (sender, receiver) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name(ISOLATED_THREAD.to_owned())
+        .spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            let _ = sender.send(result.map_err(panic_message));
+        });
+    if let Err(e) = spawned {
+        return Err(Failure::Panicked(format!("Could not spawn thread: {}", e)));
+    }
+    let result = match timeout {
+        None => receiver
+            .recv()
+            .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        Some(timeout) => receiver.recv_timeout(timeout),
+    };
+    match result {
+        Ok(result) => result.map_err(Failure::Panicked),
+        // The thread always sends before it exits, even if `f` panics
+        Err(mpsc::RecvTimeoutError::Disconnected) => unreachable!(),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Failure::TimedOut(
+            timeout.expect("Only waiting with a timeout can time out"),
+        )),
+    }
+}
+
+// Panics created with a message, like `panic!("x {}", y)` or `unwrap`, have a
+// String or &str payload. Others can't be displayed.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<unknown panic payload>".to_owned()
+    }
+}
+
+/// An input to solve a day for
+pub struct Input {
+    /// Shown in the output to tell inputs apart, e.g. the file name of an example
+    pub label: Option<String>,
+    pub path: Option<PathBuf>,
+    pub data: String,
+    /// Which answers in the answers file are for this input
+    pub kind: InputKind,
+}
+
+/// A day, with its inputs and solver if it is implemented
+pub type DayData = (Day, Option<(Vec<Input>, Solver)>);
+
+/// Get the solvers and input data for the requested days. Days without a solver are
+/// included if they were requested explicitly, else they are skipped.
+/// If `example` is set, the inputs are all the example files of the day, else it's
+/// the day's input file. Only with `all` may a day have no examples.
+pub fn load_days(
+    data_dir: &Path,
+    year: Year,
+    days: Option<Vec<Day>>,
+    all: bool,
+    example: bool,
+) -> Result<Vec<DayData>, Error> {
+    let days = Days::new(days, all)?;
+    let solvers: Vec<(Day, Option<Solver>)> = match days {
+        Days::All => registry()
+            .filter(|r| r.year == year)
+            .map(|r| (r.day, Some(r.solver)))
+            .collect(),
+        Days::Explicit(v) => v.iter().map(|&day| (day, get_solver(year, day))).collect(),
+    };
+    // If nothing to do, don't check the data directory
+    if solvers.iter().all(|(_, s)| s.is_none()) {
+        return Ok(solvers.into_iter().map(|(day, _)| (day, None)).collect());
+    }
+    // Read input files
+    if !data_dir.is_dir() {
+        return Err(Error::NotADirectory(data_dir.to_owned()));
+    }
+    // Load the data in as String, for all requested days that have a solver
+    solvers
+        .into_iter()
+        .map(|(day, maybe_solver)| {
+            if let Some(solver) = maybe_solver {
+                let inputs = if example {
+                    let examples = load_examples(data_dir, day)?;
+                    // With --all, a day without examples shouldn't stop the others from
+                    // being solved, so it is reported as having none instead
+                    if examples.is_empty() && !all {
+                        return Err(Error::Io(
+                            format!("No example files found for day {}", day),
+                            std::io::ErrorKind::NotFound.into(),
+                        ));
+                    }
+                    examples
+                } else {
+                    vec![Input {
+                        label: None,
+                        path: Some(input_path(data_dir, day)),
+                        data: load_input(data_dir, day)?,
+                        kind: InputKind::Real,
+                    }]
+                };
+                Ok((day, Some((inputs, solver))))
+            } else {
+                Ok((day, None))
+            }
+        })
+        .collect()
+}
+
+/// All example files of the day, e.g. "day01.example.txt", labelled with their names
+pub fn load_examples(data_dir: &Path, day: Day) -> Result<Vec<Input>, Error> {
+    example_paths(data_dir, day)
+        .into_iter()
+        .enumerate()
+        .map(|(i, path)| {
+            Ok(Input {
+                label: path.file_name().map(|s| s.to_string_lossy().into_owned()),
+                data: read_file(&path)?,
+                path: Some(path),
+                kind: InputKind::Example(i + 1),
+            })
+        })
+        .collect()
+}
+
+/// The given files, each labelled with its path. "-" is read from stdin. They are
+/// checked against the answers for the real input.
+pub fn read_inputs(paths: &[PathBuf]) -> Result<Vec<Input>, Error> {
+    paths
+        .iter()
+        .map(|path| {
+            if path.as_os_str() == "-" {
+                let data = std::io::read_to_string(std::io::stdin())
+                    .map_err(|e| Error::Io("Could not read input from stdin".to_owned(), e))?;
+                Ok(Input {
+                    label: Some("stdin".to_owned()),
+                    path: None,
+                    data,
+                    kind: InputKind::Real,
+                })
+            } else {
+                Ok(Input {
+                    label: Some(path.display().to_string()),
+                    data: read_file(path)?,
+                    path: Some(path.clone()),
+                    kind: InputKind::Real,
+                })
+            }
+        })
+        .collect()
+}
+
+/// The parts to run, if the user optionally selected only one part
+pub fn parts(part: Option<u8>) -> Vec<u8> {
+    part.map_or(vec![1, 2], |p| vec![p])
+}
+
+/// How days are solved
+pub struct SolveOptions {
+    /// Only solve this part
+    pub part: Option<u8>,
+    /// Number of threads to solve days on. 0 uses one thread per CPU
+    pub jobs: usize,
+    /// Give up on an input if it takes longer than this
+    pub timeout: Option<Duration>,
+}
+
+/// Totals over all solved inputs
+pub struct Summary {
+    pub wall_time: Duration,
+    /// Number of threads the inputs were solved on
+    pub jobs: usize,
+    /// Sum of the times of the inputs
+    pub summed: Duration,
+    /// Number of answers that did not match the answers file
+    pub n_wrong: usize,
+    /// Number of inputs that panicked or timed out
+    pub n_failed: usize,
+}
+
+impl Summary {
+    /// An error if any input failed, or else if any answer was wrong
+    pub fn result(&self) -> Result<(), Error> {
+        if self.n_failed > 0 {
+            return Err(Error::FailedDays(self.n_failed));
+        }
+        if self.n_wrong > 0 {
+            return Err(Error::WrongAnswers(self.n_wrong));
+        }
+        Ok(())
+    }
+}
+
+/// Solve each input of each day, possibly in parallel, and pass the reports to `output`
+/// in the order of the days and their inputs. Answers are checked if `answers` is given.
+pub fn solve_days(
+    data: Vec<DayData>,
+    answers: Option<&Answers>,
+    options: &SolveOptions,
+    mut output: impl FnMut(DayReport),
+) -> Summary {
+    // Each input of each day is solved separately. Days with nothing to solve have their
+    // status right away.
+    let items: Vec<_> = data
+        .into_iter()
+        .flat_map(|(day, maybe_data_solver)| -> Vec<_> {
+            match maybe_data_solver {
+                None => vec![(day, Err(Status::Unimplemented))],
+                Some((inputs, _)) if inputs.is_empty() => vec![(day, Err(Status::NoExamples))],
+                Some((inputs, solver)) => inputs
+                    .into_iter()
+                    .map(|input| (day, Ok((input, solver))))
+                    .collect(),
+            }
+        })
+        .collect();
+    let parts = parts(options.part);
+    let jobs = match options.jobs {
+        0 => available_jobs(),
+        n => n,
+    };
+    let begin = Instant::now();
+    let mut summary = Summary {
+        wall_time: Duration::ZERO,
+        jobs,
+        summed: Duration::ZERO,
+        n_wrong: 0,
+        n_failed: 0,
+    };
+    run_ordered(
+        items,
+        jobs,
+        |(day, maybe_input_solver)| match maybe_input_solver {
+            Err(status) => DayReport {
+                day,
+                label: None,
+                path: None,
+                status,
+            },
+            Ok((input, solver)) => {
+                solve_input(day, input, solver, &parts, answers, options.timeout)
+            }
+        },
+        |report| {
+            match &report.status {
+                Status::Solved(solved) => {
+                    summary.summed += solved.time;
+                    summary.n_wrong += solved
+                        .parts
+                        .iter()
+                        .filter(|p| matches!(p.verdict, Some(Verdict::Wrong(_))))
+                        .count();
+                }
+                Status::Failed(_) => summary.n_failed += 1,
+                Status::Unimplemented | Status::NoExamples => (),
+            }
+            output(report);
+        },
+    );
+    summary.wall_time = begin.elapsed();
+    summary
+}
+
+/// Solve the parts of the day for the input, and check the answers if `answers` is given.
+/// A panic or timeout is reported as a failure, rather than stopping the caller.
+pub fn solve_input(
+    day: Day,
+    input: Input,
+    solver: Solver,
+    parts: &[u8],
+    answers: Option<&Answers>,
+    timeout: Option<Duration>,
+) -> DayReport {
+    let Input {
+        label,
+        path,
+        data,
+        kind,
+    } = input;
+    let parts = parts.to_vec();
+    let status = match isolate(move || time_solver(solver, &data, &parts), timeout) {
+        Ok(mut solved) => {
+            for part in solved.parts.iter_mut() {
+                part.verdict = answers.map(|a| a.check(day, kind, part.part, &part.answer));
+            }
+            Status::Solved(solved)
+        }
+        Err(failure) => Status::Failed(failure),
+    };
+    DayReport {
+        day,
+        label,
+        path,
+        status,
+    }
+}
+
+fn time_solver(solver: Solver, data: &str, parts: &[u8]) -> Solved {
+    let begin = Instant::now();
+    let puzzle = solver(data);
+    let parse_time = begin.elapsed();
+    let parts: Vec<_> = parts
+        .iter()
+        .map(|&part| {
+            let begin = Instant::now();
+            let answer = puzzle.part(part);
+            PartReport {
+                part,
+                answer,
+                time: begin.elapsed(),
+                verdict: None,
+            }
+        })
+        .collect();
+    Solved {
+        time: begin.elapsed(),
+        parse_time,
+        parts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{isolate, load_days, run_ordered, solve_days, Failure, SolveOptions};
+    use crate::{
+        answers::{Answers, Verdict},
+        error::Error,
+        report::Status,
+        test_util::TempDir,
+        Answer, Day, Year,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_run_ordered() {
+        for jobs in [1, 4] {
+            let mut results = Vec::new();
+            // Early items take the longest, so they finish last when run in parallel
+            run_ordered(
+                (0..8u64).collect(),
+                jobs,
+                |i| {
+                    std::thread::sleep(Duration::from_millis(8 - i));
+                    i * i
+                },
+                |r| results.push(r),
+            );
+            assert_eq!(results, vec![0, 1, 4, 9, 16, 25, 36, 49]);
+        }
+    }
+
+    #[test]
+    fn test_isolate() {
+        assert_eq!(isolate(|| 1 + 1, None), Ok(2));
+        assert_eq!(
+            isolate(|| -> u8 { panic!("bad input: {}", 42) }, None),
+            Err(Failure::Panicked("bad input: 42".to_owned()))
+        );
+        let timeout = Duration::from_millis(10);
+        assert_eq!(
+            isolate(|| std::thread::sleep(Duration::from_secs(5)), Some(timeout)),
+            Err(Failure::TimedOut(timeout))
+        );
+    }
+
+    #[test]
+    fn test_solve_days() {
+        let dir = TempDir::new("runner");
+        std::fs::write(dir.path().join("day01.txt"), "3   4\n4   3\n2   5\n1   3\n").unwrap();
+        std::fs::write(
+            dir.path().join("answers.toml"),
+            "[day01]\npart1 = 5\npart2 = 1\n",
+        )
+        .unwrap();
+        let answers = Answers::load(&dir.path().join("answers.toml")).unwrap();
+        let days = vec![Day::new(1).unwrap(), Day::new(25).unwrap()];
+        let data = load_days(dir.path(), Year::default(), Some(days), false, false).unwrap();
+        let options = SolveOptions {
+            part: None,
+            jobs: 2,
+            timeout: None,
+        };
+        let mut reports = Vec::new();
+        let summary = solve_days(data, Some(&answers), &options, |r| reports.push(r));
+        assert_eq!(reports.len(), 2);
+        let solved = reports[0].status.solved().unwrap();
+        let results: Vec<_> = solved
+            .parts
+            .iter()
+            .map(|p| (p.answer.clone(), p.verdict.clone()))
+            .collect();
+        assert_eq!(
+            results,
+            [
+                (Answer::Solved("5".to_owned()), Some(Verdict::Correct)),
+                (
+                    Answer::Solved("10".to_owned()),
+                    Some(Verdict::Wrong("1".to_owned()))
+                ),
+            ]
+        );
+        assert!(matches!(reports[1].status, Status::Unimplemented));
+        assert_eq!((summary.n_wrong, summary.n_failed), (1, 0));
+        assert!(matches!(summary.result(), Err(Error::WrongAnswers(1))));
+    }
+
+    #[test]
+    fn test_load_examples() {
+        let dir = TempDir::new("examples");
+        std::fs::write(dir.path().join("day01.example.txt"), "1   2\n").unwrap();
+        let data = load_days(dir.path(), Year::default(), None, true, true).unwrap();
+        let n_inputs: Vec<_> = data
+            .iter()
+            .map(|(day, d)| (day.get(), d.as_ref().map(|(inputs, _)| inputs.len())))
+            .collect();
+        assert_eq!(&n_inputs[..2], [(1, Some(1)), (2, Some(0))]);
+        let mut statuses = Vec::new();
+        let options = SolveOptions {
+            part: None,
+            jobs: 1,
+            timeout: None,
+        };
+        solve_days(data, None, &options, |r| statuses.push(r.status));
+        assert!(matches!(statuses[0], Status::Solved(_)));
+        assert!(matches!(statuses[1], Status::NoExamples));
+        // Explicitly requested days must have examples
+        let days = Some(vec![Day::new(2).unwrap()]);
+        assert!(load_days(dir.path(), Year::default(), days, false, true).is_err());
+    }
+}
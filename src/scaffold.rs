@@ -0,0 +1,133 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{error::Error, Day, Year};
+
+// The placeholder "NN" is replaced with the zero-padded day
+const TEMPLATE: &str = r#"use crate::{Solution, UnimplementedPartTwo};
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Parsed = ();
+    type Answer1 = UnimplementedPartTwo;
+    type Answer2 = UnimplementedPartTwo;
+
+    fn parse(_s: &str) -> Self::Parsed {}
+
+    fn part_one(_: &Self::Parsed) -> UnimplementedPartTwo {
+        UnimplementedPartTwo
+    }
+
+    fn part_two(_: &Self::Parsed) -> UnimplementedPartTwo {
+        UnimplementedPartTwo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Solution, UnimplementedPartTwo};
+
+    static TEST_STR: &str = "";
+
+    #[test]
+    fn test() {
+        assert_eq!(
+            super::DayNN::solve(TEST_STR),
+            (UnimplementedPartTwo, UnimplementedPartTwo)
+        );
+    }
+}
+"#;
+
+/// Create a new day module from a template in the year's module, e.g. `src_dir/days/y2024`,
+/// and register it in the year's `mod.rs`. Returns the path of the new module.
+pub fn new_day(src_dir: &Path, year: Year, day: Day) -> Result<PathBuf, Error> {
+    let year_dir = src_dir.join("days").join(format!("y{}", year));
+    if !year_dir.is_dir() {
+        return Err(Error::Scaffold(format!(
+            "No module for year {}. Create {:?} with a `mod.rs` invoking `days!`, \
+            and add it to the registry in `days/mod.rs`",
+            year, year_dir
+        )));
+    }
+    let mod_path = year_dir.join("mod.rs");
+    let mod_rs = std::fs::read_to_string(&mod_path)
+        .map_err(|e| Error::Io(format!("Could not read \"{:?}\"", mod_path), e))?;
+    // Do this first, such that we don't create the file if the registration fails
+    let mod_rs = register_day(&mod_rs, day)?;
+    let path = year_dir.join(format!("day{}.rs", day));
+    let mut file = File::create_new(&path)
+        .map_err(|e| Error::Io(format!("Could not create new file at \"{:?}\"", path), e))?;
+    file.write_all(TEMPLATE.replace("NN", &day.to_string()).as_bytes())
+        .map_err(|e| Error::Io(format!("Error when writing to \"{:?}\"", path), e))?;
+    std::fs::write(&mod_path, mod_rs)
+        .map_err(|e| Error::Io(format!("Error when writing to \"{:?}\"", mod_path), e))?;
+    Ok(path)
+}
+
+// Add a line for the day to the `days!` invocation in the year's module, keeping it sorted
+fn register_day(mod_rs: &str, day: Day) -> Result<String, Error> {
+    let invalid = |msg: &str| Error::Scaffold(format!("In mod.rs of year: {}", msg));
+    let mut lines: Vec<&str> = mod_rs.lines().collect();
+    let start = lines
+        .iter()
+        .position(|&line| line == "days! {")
+        .ok_or_else(|| invalid("Could not find the `days!` invocation"))?;
+    let mut insert_at = None;
+    // Skip the line with the year
+    for (i, line) in lines.iter().enumerate().skip(start + 2) {
+        if line.trim() == "}" {
+            insert_at = insert_at.or(Some(i));
+            break;
+        }
+        let existing = line
+            .split_once("=>")
+            .and_then(|(n, _)| n.trim().parse::<u8>().ok())
+            .ok_or_else(|| invalid(&format!("Could not parse line \"{}\"", line)))?;
+        if existing == day.get() {
+            return Err(invalid(&format!("Day {} is already registered", day)));
+        } else if existing > day.get() && insert_at.is_none() {
+            insert_at = Some(i);
+        }
+    }
+    let insert_at = insert_at.ok_or_else(|| invalid("`days!` invocation is not closed"))?;
+    let new_line = format!("    {} => day{}::Day{},", day.get(), day, day);
+    lines.insert(insert_at, &new_line);
+    let mut result = lines.join("\n");
+    result.push('\n');
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::register_day;
+    use crate::Day;
+
+    static MOD_RS: &str = "use crate::Registration;
+
+days! {
+    2024;
+    1 => day01::Day01,
+    12 => day12::Day12,
+}
+";
+
+    #[test]
+    fn test_register() {
+        let day = |n| Day::new(n).unwrap();
+        assert_eq!(
+            register_day(MOD_RS, day(9)).unwrap(),
+            MOD_RS.replace("    12 =>", "    9 => day09::Day09,\n    12 =>")
+        );
+        assert_eq!(
+            register_day(MOD_RS, day(13)).unwrap(),
+            MOD_RS.replace("}\n", "    13 => day13::Day13,\n}\n")
+        );
+        assert!(register_day(MOD_RS, day(12)).is_err());
+        assert!(register_day("", day(2)).is_err());
+    }
+}
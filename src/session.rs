@@ -0,0 +1,208 @@
+use std::{
+    fmt::Display,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
+
+use crate::error::Error;
+
+pub const ENV_VAR: &str = "AOC_SESSION";
+
+/// Where a session key was obtained from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Argument,
+    Environment,
+    ConfigFile(PathBuf),
+    Prompt,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Argument => write!(f, "--session-key"),
+            Self::Environment => write!(f, "environment variable {}", ENV_VAR),
+            Self::ConfigFile(p) => write!(f, "config file {:?}", p),
+            Self::Prompt => write!(f, "prompt"),
+        }
+    }
+}
+
+pub struct SessionKey {
+    pub key: String,
+    pub source: Source,
+}
+
+/// Path of the config file, i.e. "advent2024/config.toml" in the XDG config directory.
+/// This is `$XDG_CONFIG_HOME`, or `$HOME/.config` if that is not set.
+pub fn config_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(config_dir.join("advent2024").join("config.toml"))
+}
+
+/// Get the session key from, in order: The argument, the `AOC_SESSION` environment
+/// variable, the `session_key` field of the config file, or an interactive prompt.
+pub fn resolve(argument: Option<&str>) -> Result<SessionKey, Error> {
+    let env = std::env::var(ENV_VAR).ok();
+    resolve_from(argument, env.as_deref(), config_path().as_deref(), prompt)
+}
+
+// Ask for the key, unless there is no terminal to ask on
+fn prompt() -> Result<Option<String>, Error> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(None);
+    }
+    rpassword::prompt_password("Advent of Code session key: ")
+        .map(Some)
+        .map_err(|e| Error::Io("Could not read session key from prompt".to_owned(), e))
+}
+
+// Like `resolve`, with the value of the environment variable, the path of the config file
+// and the prompt passed in
+fn resolve_from(
+    argument: Option<&str>,
+    env: Option<&str>,
+    config: Option<&Path>,
+    prompt: impl FnOnce() -> Result<Option<String>, Error>,
+) -> Result<SessionKey, Error> {
+    let found = |key: &str, source| {
+        Ok(SessionKey {
+            key: key.trim().to_owned(),
+            source,
+        })
+    };
+    if let Some(key) = argument {
+        return found(key, Source::Argument);
+    }
+    if let Some(key) = env.filter(|s| !s.is_empty()) {
+        return found(key, Source::Environment);
+    }
+    if let Some(path) = config {
+        if let Some(key) = read_config_string(path, "session_key")? {
+            return found(&key, Source::ConfigFile(path.to_owned()));
+        }
+    }
+    if let Some(key) = prompt()? {
+        return found(&key, Source::Prompt);
+    }
+    Err(Error::MissingSessionKey)
+}
+
+/// The string field `key` of the config file, and the path of the file, if the file
+/// exists and has the field
+pub fn config_string(key: &str) -> Result<Option<(String, PathBuf)>, Error> {
+    let Some(path) = config_path() else {
+        return Ok(None);
+    };
+    Ok(read_config_string(&path, key)?.map(|value| (value, path)))
+}
+
+// The string field `key` of the config file at the path, if the file exists and has the field
+fn read_config_string(path: &Path, key: &str) -> Result<Option<String>, Error> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| Error::Io(format!("Could not read config file \"{:?}\"", path), e))?;
+    let table: toml::Table = text
+        .parse()
+        .map_err(|e| Error::InvalidConfigFile(format!("{:?}: {}", path, e)))?;
+    match table.get(key) {
+        None => Ok(None),
+        Some(toml::Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(Error::InvalidConfigFile(format!(
+            "{:?}: {} must be a string",
+            path, key
+        ))),
+    }
+}
+
+/// Whether the file can be read by users other than its owner
+#[cfg(unix)]
+pub fn has_loose_permissions(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o077 != 0)
+}
+
+#[cfg(not(unix))]
+pub fn has_loose_permissions(_: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{has_loose_permissions, resolve_from, Source};
+    use crate::{error::Error, test_util::TempDir};
+    use std::path::Path;
+
+    type Prompt = fn() -> Result<Option<String>, Error>;
+
+    // The key and its source, from the other sources than the process environment
+    fn resolve(
+        argument: Option<&str>,
+        env: Option<&str>,
+        config: Option<&Path>,
+        prompt: Prompt,
+    ) -> Result<(String, Source), Error> {
+        resolve_from(argument, env, config, prompt).map(|session| (session.key, session.source))
+    }
+
+    #[test]
+    fn test_resolve() {
+        let dir = TempDir::new("session");
+        let config = dir.path().join("config.toml");
+        std::fs::write(&config, "session_key = \"from config\"\n").unwrap();
+        let no_prompt: Prompt = || panic!("Prompted although a key was found");
+        let prompt: Prompt = || Ok(Some("from prompt\n".to_owned()));
+
+        let key = |s: &str| s.to_owned();
+        assert_eq!(
+            resolve(Some("arg"), Some("env"), Some(&config), no_prompt).unwrap(),
+            (key("arg"), Source::Argument)
+        );
+        assert_eq!(
+            resolve(None, Some("env"), Some(&config), no_prompt).unwrap(),
+            (key("env"), Source::Environment)
+        );
+        // An empty environment variable counts as unset
+        assert_eq!(
+            resolve(None, Some(""), Some(&config), no_prompt).unwrap(),
+            (key("from config"), Source::ConfigFile(config.clone()))
+        );
+        let missing = dir.path().join("missing.toml");
+        assert_eq!(
+            resolve(None, None, Some(&missing), prompt).unwrap(),
+            (key("from prompt"), Source::Prompt)
+        );
+        std::fs::write(&config, "user_agent = \"me\"\n").unwrap();
+        assert_eq!(
+            resolve(None, None, Some(&config), prompt).unwrap(),
+            (key("from prompt"), Source::Prompt)
+        );
+        assert!(matches!(
+            resolve(None, None, None, || Ok(None)),
+            Err(Error::MissingSessionKey)
+        ));
+        std::fs::write(&config, "session_key = 1\n").unwrap();
+        assert!(matches!(
+            resolve(None, None, Some(&config), no_prompt),
+            Err(Error::InvalidConfigFile(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_loose_permissions() {
+        use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+        let dir = TempDir::new("permissions");
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "session_key = \"abc\"\n").unwrap();
+        std::fs::set_permissions(&path, Permissions::from_mode(0o644)).unwrap();
+        assert!(has_loose_permissions(&path));
+        std::fs::set_permissions(&path, Permissions::from_mode(0o600)).unwrap();
+        assert!(!has_loose_permissions(&path));
+    }
+}
@@ -0,0 +1,158 @@
+use reqwest::blocking::Client;
+use std::{fmt::Display, time::Duration};
+
+use crate::{error::Error, Day, Year};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Wrong, but the server did not say whether it was too high or too low
+    Wrong,
+    RateLimited(Duration),
+    AlreadySolved,
+    // The response did not match any of the known patterns. Contains the response text.
+    Unrecognized(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer!"),
+            Self::TooHigh => write!(f, "Wrong answer: Too high"),
+            Self::TooLow => write!(f, "Wrong answer: Too low"),
+            Self::Wrong => write!(f, "Wrong answer"),
+            Self::RateLimited(d) => write!(f, "Rate limited, wait {:?} before trying again", d),
+            Self::AlreadySolved => write!(f, "This part is already solved"),
+            Self::Unrecognized(s) => write!(f, "Could not understand response:\n{}", s),
+        }
+    }
+}
+
+pub fn submit(
+    client: &Client,
+    base_url: &str,
+    year: Year,
+    day: Day,
+    part: u8,
+    answer: &str,
+) -> Result<Outcome, Error> {
+    let url = format!("{}/{}/day/{}/answer", base_url, year, day.get());
+    let part = part.to_string();
+    let resp = client
+        .post(url.as_str())
+        .form(&[("level", part.as_str()), ("answer", answer)])
+        .send()?;
+    Ok(parse_response(&resp.text()?))
+}
+
+fn parse_response(html: &str) -> Outcome {
+    if html.contains("That's the right answer") {
+        Outcome::Correct
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if html.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if html.contains("You gave an answer too recently") {
+        Outcome::RateLimited(parse_wait(html).unwrap_or_default())
+    } else if html.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unrecognized(html.to_owned())
+    }
+}
+
+// The server's message reads e.g. "You have 1m 23s left to wait."
+fn parse_wait(html: &str) -> Option<Duration> {
+    let re = regex::Regex::new(r"You have ((?:\d+[hms]\s*)+) left to wait").unwrap();
+    let cap = re.captures(html)?;
+    cap[1]
+        .split_ascii_whitespace()
+        .try_fold(0, |secs, token| {
+            let (n, unit) = token.split_at(token.len() - 1);
+            let n: u64 = n.parse().ok()?;
+            let mult = match unit {
+                "h" => 3600,
+                "m" => 60,
+                _ => 1,
+            };
+            Some(secs + n * mult)
+        })
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_response, submit, Outcome};
+    use crate::{test_util::serve, Day, Year};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse() {
+        let wrap = |s: &str| format!("<main><article><p>{}</p></article></main>", s);
+        assert_eq!(
+            parse_response(&wrap(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_response(&wrap(
+                "That's not the right answer; your answer is too high."
+            )),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            parse_response(&wrap(
+                "That's not the right answer; your answer is too low."
+            )),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_response(&wrap("That's not the right answer.")),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            parse_response(&wrap(
+                "You gave an answer too recently. You have 1m 23s left to wait."
+            )),
+            Outcome::RateLimited(Duration::from_secs(83))
+        );
+        assert_eq!(
+            parse_response(&wrap(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Outcome::AlreadySolved
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, server) = serve(vec![(
+            "200 OK",
+            "<article><p>That's the right answer!</p></article>",
+        )]);
+        let client = reqwest::blocking::Client::new();
+        let outcome = submit(
+            &client,
+            &base_url,
+            Year::default(),
+            Day::new(7).unwrap(),
+            2,
+            "1234",
+        )
+        .unwrap();
+        let requests = server.join().unwrap();
+        let request = &requests[0];
+        assert!(request.starts_with("POST /2024/day/7/answer "));
+        assert!(request.ends_with("level=2&answer=1234"));
+        assert_eq!(outcome, Outcome::Correct);
+    }
+}
@@ -0,0 +1,82 @@
+// Helpers shared by the tests of several modules
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    thread::JoinHandle,
+};
+
+/// A directory in the system's temporary directory, which is removed when dropped.
+/// The name includes the process ID, such that concurrent test runs don't collide.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("advent2024-{}-{}", name, std::process::id()));
+        // Left over from a previous run that panicked, if it exists
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Serve each of the responses, given as status line and body, to one HTTP request on
+/// localhost, then shut down. Returns the base URL of the server, and a handle to join
+/// for the requests it received, as text.
+pub fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = read_request(&mut stream);
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    (base_url, server)
+}
+
+// Read the headers, then as much of the body as the Content-Length header says
+fn read_request(stream: &mut impl Read) -> String {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    let mut expected_len = None;
+    while expected_len.is_none_or(|len| request.len() < len) {
+        let n = stream.read(&mut buf).unwrap();
+        assert!(n > 0, "Connection closed before the request was complete");
+        request.extend_from_slice(&buf[..n]);
+        if expected_len.is_none() {
+            let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+                continue;
+            };
+            let headers = String::from_utf8_lossy(&request[..end]).to_ascii_lowercase();
+            let body_len = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map_or(0, |len| len.trim().parse().unwrap());
+            expected_len = Some(end + 4 + body_len);
+        }
+    }
+    String::from_utf8(request).unwrap()
+}
@@ -0,0 +1,442 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    error::Error,
+    get_registration,
+    grid::{Grid, Pos},
+    Day, Year,
+};
+
+/// A cell of a visualized grid: A character for the terminal, and a color for both the
+/// terminal and images
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tile {
+    pub glyph: char,
+    pub color: [u8; 3],
+}
+
+impl Tile {
+    pub const fn new(glyph: char, color: [u8; 3]) -> Self {
+        Tile { glyph, color }
+    }
+}
+
+// Such that a grid of tiles can be printed as plain text
+impl From<Tile> for char {
+    fn from(tile: Tile) -> char {
+        tile.glyph
+    }
+}
+
+/// A grid that changes step by step. Only the changes of each step are stored, so
+/// animations of many steps on large grids stay small.
+pub struct Animation {
+    initial: Grid<Tile>,
+    current: Grid<Tile>,
+    steps: Vec<Vec<(Pos, Tile)>>,
+    // Changes of the step that is not yet ended
+    changes: Vec<(Pos, Tile)>,
+}
+
+impl Animation {
+    pub fn new(grid: Grid<Tile>) -> Self {
+        Animation {
+            current: grid.clone(),
+            initial: grid,
+            steps: Vec::new(),
+            changes: Vec::new(),
+        }
+    }
+
+    /// The tile at the position, with all changes so far
+    pub fn get(&self, pos: Pos) -> Tile {
+        self.current[pos]
+    }
+
+    /// Change the tile at the position in the current step
+    pub fn set(&mut self, pos: Pos, tile: Tile) {
+        if self.current[pos] != tile {
+            self.current[pos] = tile;
+            self.changes.push((pos, tile));
+        }
+    }
+
+    /// End the current step, such that the next changes are shown after it. Steps
+    /// without changes are skipped.
+    pub fn step(&mut self) {
+        if !self.changes.is_empty() {
+            self.steps.push(std::mem::take(&mut self.changes));
+        }
+    }
+
+    pub fn n_steps(&self) -> usize {
+        self.steps.len() + !self.changes.is_empty() as usize
+    }
+
+    /// The grid after all steps
+    pub fn last_frame(&self) -> &Grid<Tile> {
+        &self.current
+    }
+
+    // The changes of each frame, when showing `steps_per_frame` steps per frame
+    fn frames(&self, steps_per_frame: usize) -> impl Iterator<Item = Vec<(Pos, Tile)>> + '_ {
+        let steps = self.steps.as_slice();
+        let last = Some(&self.changes).filter(|c| !c.is_empty());
+        steps
+            .chunks(steps_per_frame.max(1))
+            .map(|chunk| chunk.concat())
+            .chain(last.cloned())
+    }
+}
+
+/// A day that can show how it solves an input, usually a day with a grid as input
+pub trait Visualize {
+    /// Animate solving the part of the day for the input
+    fn visualize(input: &str, part: u8) -> Animation;
+}
+
+/// Animates solving a part of a day for an input, as registered in the `days` module
+pub type Visualizer = fn(&str, u8) -> Animation;
+
+/// Get the visualizer of the given day, if the day is implemented and has one
+pub fn get_visualizer(year: Year, day: Day) -> Option<Visualizer> {
+    get_registration(year, day).and_then(|r| r.visualizer)
+}
+
+// The tile as text in its color, using a 24-bit color escape code
+fn paint(tile: Tile) -> String {
+    let [r, g, b] = tile.color;
+    format!("\x1b[38;2;{};{};{}m{}", r, g, b, tile.glyph)
+}
+
+// Set by Ctrl-C while an animation plays, to stop it
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+// Resets the color and shows the cursor below the grid when dropped, such that the
+// terminal is left usable however playing ends
+struct Restore<'a, W: Write> {
+    out: &'a mut W,
+    height: usize,
+}
+
+impl<W: Write> Drop for Restore<'_, W> {
+    fn drop(&mut self) {
+        // Nothing more can be done if the terminal can't be written to
+        let _ = write!(self.out, "\x1b[0m\x1b[{};1H\x1b[?25h", self.height + 1);
+        let _ = self.out.flush();
+    }
+}
+
+/// Play the animation in the terminal, drawing `steps_per_frame` steps every `delay`.
+/// Ctrl-C stops playing, rather than exiting with the cursor still hidden.
+pub fn play(
+    animation: &Animation,
+    out: &mut impl Write,
+    steps_per_frame: usize,
+    delay: Duration,
+) -> std::io::Result<()> {
+    // Fails if a handler is already set, in which case Ctrl-C is already taken care of
+    let _ = ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::Relaxed));
+    let out = Restore {
+        out,
+        height: animation.initial.height(),
+    };
+    // Clear the screen, and hide the cursor while drawing
+    write!(out.out, "\x1b[2J\x1b[H\x1b[?25l")?;
+    for row in animation.initial.rows() {
+        let line: String = row.iter().map(|&tile| paint(tile)).collect();
+        writeln!(out.out, "{}\x1b[0m", line)?;
+    }
+    out.out.flush()?;
+    for frame in animation.frames(steps_per_frame) {
+        thread::sleep(delay);
+        if INTERRUPTED.load(Ordering::Relaxed) {
+            break;
+        }
+        // Only the changed tiles are redrawn, by moving the cursor to each. Terminal rows
+        // and columns count from 1.
+        for (pos, tile) in frame {
+            write!(
+                out.out,
+                "\x1b[{};{}H{}",
+                pos.row + 1,
+                pos.col + 1,
+                paint(tile)
+            )?;
+        }
+        out.out.flush()?;
+    }
+    Ok(())
+}
+
+fn image_error(path: &Path, e: impl ToString) -> Error {
+    Error::Io(
+        format!("Could not write image \"{:?}\"", path),
+        std::io::Error::other(e.to_string()),
+    )
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>, Error> {
+    File::create(path)
+        .map(BufWriter::new)
+        .map_err(|e| Error::Io(format!("Could not create file \"{:?}\"", path), e))
+}
+
+// A rectangle of an image, in pixels, with the bytes of its pixels row by row
+struct Image {
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+// The image of a rectangle of tiles in the grid, with each tile a square of `scale` by
+// `scale` pixels, and each pixel of a tile given by `pixel`
+fn render<const N: usize>(
+    grid: &Grid<Tile>,
+    (top, left): (usize, usize),
+    (rows, cols): (usize, usize),
+    scale: usize,
+    pixel: &impl Fn(Tile) -> [u8; N],
+) -> Image {
+    let mut pixels = Vec::with_capacity(rows * cols * scale * scale * N);
+    for row in top..top + rows {
+        let line: Vec<u8> = (left..left + cols)
+            .flat_map(|col| pixel(grid[Pos::new(row, col)]).repeat(scale))
+            .collect();
+        pixels.extend(line.repeat(scale));
+    }
+    Image {
+        left: left * scale,
+        top: top * scale,
+        width: cols * scale,
+        height: rows * scale,
+        pixels,
+    }
+}
+
+// The images of the frames of the animation. The first frame is the whole grid, and later
+// frames only cover the rectangle with the tiles that changed.
+fn frame_images<'a, const N: usize>(
+    animation: &'a Animation,
+    steps_per_frame: usize,
+    scale: usize,
+    pixel: impl Fn(Tile) -> [u8; N] + 'a,
+) -> impl Iterator<Item = Image> + 'a {
+    let mut grid = animation.initial.clone();
+    let size = (grid.height(), grid.width());
+    let first = render(&grid, (0, 0), size, scale, &pixel);
+    let later = animation.frames(steps_per_frame).map(move |changes| {
+        for &(pos, tile) in &changes {
+            grid[pos] = tile;
+        }
+        let top = changes.iter().map(|(p, _)| p.row).min().unwrap_or(0);
+        let bottom = changes.iter().map(|(p, _)| p.row).max().unwrap_or(0);
+        let left = changes.iter().map(|(p, _)| p.col).min().unwrap_or(0);
+        let right = changes.iter().map(|(p, _)| p.col).max().unwrap_or(0);
+        let size = (bottom - top + 1, right - left + 1);
+        render(&grid, (top, left), size, scale, &pixel)
+    });
+    std::iter::once(first).chain(later)
+}
+
+/// Write the animation as an animated PNG (APNG), with each tile a square of `scale` by
+/// `scale` pixels. Each frame shows `steps_per_frame` steps, and lasts for `delay`.
+/// Viewers that don't support animation show the last frame instead.
+pub fn write_png(
+    path: &Path,
+    animation: &Animation,
+    scale: u32,
+    steps_per_frame: usize,
+    delay: Duration,
+) -> Result<(), Error> {
+    let error = |e: png::EncodingError| image_error(path, e);
+    let scale = scale as usize;
+    let rgb = |tile: Tile| tile.color;
+    let grid = animation.last_frame();
+    let last = render(grid, (0, 0), (grid.height(), grid.width()), scale, &rgb);
+    let n_frames = 1 + animation.frames(steps_per_frame).count();
+    let mut encoder = png::Encoder::new(create(path)?, last.width as u32, last.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(n_frames as u32, 0).map_err(error)?;
+    // The default image, which is the only one shown without animation, is not a frame
+    encoder.set_sep_def_img(true).map_err(error)?;
+    let mut writer = encoder.write_header().map_err(error)?;
+    writer.write_image_data(&last.pixels).map_err(error)?;
+    // APNG delays are a fraction of a second
+    let delay = delay.as_millis().min(u16::MAX as u128) as u16;
+    writer.set_frame_delay(delay, 1000).map_err(error)?;
+    for image in frame_images(animation, steps_per_frame, scale, rgb) {
+        // A frame must stay inside the image while it is resized and moved, so it is
+        // resized in the top left corner
+        writer.reset_frame_position().map_err(error)?;
+        writer
+            .set_frame_dimension(image.width as u32, image.height as u32)
+            .map_err(error)?;
+        writer
+            .set_frame_position(image.left as u32, image.top as u32)
+            .map_err(error)?;
+        writer.write_image_data(&image.pixels).map_err(error)?;
+    }
+    writer.finish().map_err(error)
+}
+
+/// Write the animation as an animated GIF, with each tile a square of `scale` by
+/// `scale` pixels. Each frame shows `steps_per_frame` steps, and lasts for `delay`.
+pub fn write_gif(
+    path: &Path,
+    animation: &Animation,
+    scale: u32,
+    steps_per_frame: usize,
+    delay: Duration,
+) -> Result<(), Error> {
+    let scale = scale as usize;
+    let (width, height) = (
+        animation.initial.width() * scale,
+        animation.initial.height() * scale,
+    );
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        return Err(image_error(path, "the image is too large for a GIF"));
+    }
+    // GIFs have at most 256 colors, which is plenty, since each kind of tile has one color
+    let mut palette: HashMap<[u8; 3], u8> = HashMap::new();
+    let all_tiles = animation.initial.cells().iter().chain(
+        animation
+            .steps
+            .iter()
+            .chain([&animation.changes])
+            .flatten()
+            .map(|(_, tile)| tile),
+    );
+    for tile in all_tiles {
+        let n = palette.len();
+        palette.entry(tile.color).or_insert(n as u8);
+        if palette.len() > 256 {
+            return Err(image_error(path, "a GIF can have at most 256 colors"));
+        }
+    }
+    let mut colors = vec![0; palette.len() * 3];
+    for (color, &i) in &palette {
+        colors[i as usize * 3..][..3].copy_from_slice(color);
+    }
+    let mut encoder = gif::Encoder::new(create(path)?, width as u16, height as u16, &colors)
+        .map_err(|e| image_error(path, e))?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|e| image_error(path, e))?;
+    // GIF delays are in hundredths of a second, and most viewers slow down shorter delays
+    let delay = (delay.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+    let index = |tile: Tile| [palette[&tile.color]];
+    for image in frame_images(animation, steps_per_frame, scale, index) {
+        let frame = gif::Frame {
+            delay,
+            top: image.top as u16,
+            left: image.left as u16,
+            height: image.height as u16,
+            width: image.width as u16,
+            buffer: Cow::Owned(image.pixels),
+            ..Default::default()
+        };
+        encoder
+            .write_frame(&frame)
+            .map_err(|e| image_error(path, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_gif, write_png, Animation, Tile};
+    use crate::{
+        grid::{Grid, Pos},
+        test_util::TempDir,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_animation() {
+        const FLOOR: Tile = Tile::new('.', [0, 0, 0]);
+        const ROBOT: Tile = Tile::new('@', [255, 0, 0]);
+        let mut animation = Animation::new(Grid::parse("...\n...\n", |_| FLOOR));
+        for col in 0..3 {
+            if col > 0 {
+                animation.set(Pos::new(1, col - 1), FLOOR);
+            }
+            animation.set(Pos::new(1, col), ROBOT);
+            animation.step();
+        }
+        // Setting a tile to what it already is does not make a step
+        animation.set(Pos::new(0, 0), FLOOR);
+        animation.step();
+        assert_eq!(animation.n_steps(), 3);
+        assert_eq!(animation.last_frame().to_string(), "...\n..@\n");
+
+        let dir = TempDir::new("visualize");
+        let path = dir.path().join("animation.gif");
+        write_gif(&path, &animation, 2, 2, Duration::from_millis(100)).unwrap();
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(std::fs::File::open(&path).unwrap())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 4));
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((
+                frame.left,
+                frame.top,
+                frame.width,
+                frame.height,
+                frame.delay,
+            ));
+        }
+        // The whole grid, then steps 1-2, where the robot appears and moves, then step 3
+        assert_eq!(
+            frames,
+            [(0, 0, 6, 4, 10), (0, 2, 4, 2, 10), (2, 2, 4, 2, 10)]
+        );
+
+        let path = dir.path().join("animation.png");
+        write_png(&path, &animation, 2, 2, Duration::from_millis(100)).unwrap();
+        let file = std::io::BufReader::new(std::fs::File::open(&path).unwrap());
+        let mut reader = png::Decoder::new(file).read_info().unwrap();
+        assert_eq!(
+            reader.info().animation_control.map(|a| a.num_frames),
+            Some(3)
+        );
+        // The default image is the last frame, with the robot in the bottom right corner
+        let mut buf = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut buf).unwrap();
+        let red_pixels: Vec<_> = buf
+            .chunks(3)
+            .enumerate()
+            .filter(|(_, rgb)| rgb == &[255, 0, 0])
+            .map(|(i, _)| (i % 6, i / 6))
+            .collect();
+        assert_eq!(red_pixels, [(4, 2), (5, 2), (4, 3), (5, 3)]);
+        let mut frames = Vec::new();
+        for _ in 0..3 {
+            let frame = reader.next_frame_info().unwrap();
+            frames.push((
+                frame.x_offset,
+                frame.y_offset,
+                frame.width,
+                frame.height,
+                frame.delay_num,
+            ));
+        }
+        assert_eq!(
+            frames,
+            [(0, 0, 6, 4, 100), (0, 2, 4, 2, 100), (2, 2, 4, 2, 100)]
+        );
+    }
+}